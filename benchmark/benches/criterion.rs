@@ -0,0 +1,190 @@
+//! Criterion versions of the scenarios in `src/main.rs`.
+//!
+//! Setup (allocating and fragmenting) happens outside of the measured closures,
+//! so only the reads are timed.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use pierce::{HashedPierce, Pierce, PierceVec, StableDeref};
+use std::{
+    collections::{hash_map::RandomState, HashSet},
+    hint::black_box,
+    sync::Arc,
+};
+
+const SMALL_NUM: usize = 65536;
+const MEDIUM_NUM: usize = 1_000_000;
+const BIG_NUM: usize = 1_000_000;
+
+fn fragmented_box_vec(c: &mut Criterion) {
+    let v: Vec<usize> = (0..SMALL_NUM).collect();
+    // Kinda simulate memory fragmentation by creating a lot of empty vecs.
+    let mut boxes: Vec<Box<Vec<usize>>> = (0..BIG_NUM).map(|_| Box::new(vec![])).collect();
+    *boxes[BIG_NUM / 2] = v;
+    let b = std::mem::take(&mut boxes[BIG_NUM / 2]);
+
+    let mut group = c.benchmark_group("Fragmented Box<Vec<_>>");
+    group.bench_function("Normal", |bencher| {
+        bencher.iter(|| {
+            let b = black_box(&b);
+            (0..SMALL_NUM).map(|i| b.get(i).unwrap()).sum::<usize>()
+        })
+    });
+    let p = Pierce::new(b);
+    group.bench_function("Pierce", |bencher| {
+        bencher.iter(|| {
+            let p = black_box(&p);
+            (0..SMALL_NUM).map(|i| p.get(i).unwrap()).sum::<usize>()
+        })
+    });
+    group.finish();
+}
+
+// SlowBox: like Box but computes Collatz(31) every time you want to deref it.
+struct SlowBox<T>(Box<T>);
+impl<T> std::ops::Deref for SlowBox<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        let mut n = black_box(31);
+        loop {
+            match n {
+                1 => break self.0.deref(),
+                _ if n % 2 == 0 => n /= 2,
+                _ => n = n * 3 + 1,
+            }
+        }
+    }
+}
+unsafe impl<T> StableDeref for SlowBox<T> {}
+
+fn slow_box(c: &mut Criterion) {
+    let mut group = c.benchmark_group("SlowBox<_>");
+    let a: SlowBox<Vec<usize>> = SlowBox(Box::new((0..SMALL_NUM).collect()));
+    group.bench_function("Normal", |bencher| {
+        bencher.iter(|| (0..SMALL_NUM).map(|i| *a.get(i).unwrap()).sum::<usize>())
+    });
+    let p = Pierce::new(a);
+    group.bench_function("Pierce", |bencher| {
+        bencher.iter(|| (0..SMALL_NUM).map(|i| *p.get(i).unwrap()).sum::<usize>())
+    });
+    group.finish();
+}
+
+// Jump around the vec following the Collatz sequence of 65535.
+#[inline]
+fn collatz_sum(get: impl Fn(usize) -> i64) -> i64 {
+    let mut sum = 0;
+    let mut i: usize = 65535;
+    loop {
+        match i {
+            1 => break sum,
+            v if v % 2 == 1 => i = v * 3 + 1,
+            v => i = v / 2,
+        }
+        sum += get(i % MEDIUM_NUM);
+    }
+}
+
+fn vec_box_box(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Vec<Box<Box<_>>>");
+    let normal: Vec<Box<Box<i64>>> = (0..MEDIUM_NUM)
+        .map(|i| Box::new(Box::new(i as i64)))
+        .collect();
+    group.bench_function("Normal", |bencher| {
+        bencher.iter(|| collatz_sum(|i| ***black_box(&normal).get(i).unwrap()))
+    });
+    let pierce: Vec<Pierce<Box<Box<i64>>>> = normal.into_iter().map(Pierce::new).collect();
+    group.bench_function("Pierce", |bencher| {
+        bencher.iter(|| collatz_sum(|i| **black_box(&pierce).get(i).unwrap()))
+    });
+    let pierce_vec: PierceVec<Box<Box<i64>>> = pierce.into_iter().map(Pierce::into_outer).collect();
+    group.bench_function("PierceVec", |bencher| {
+        bencher.iter(|| collatz_sum(|i| *black_box(&pierce_vec).get(i).unwrap()))
+    });
+    group.finish();
+}
+
+// Sum every item in order. PierceVec keeps its cached pointers in their own array, so only that is read.
+fn vec_box_box_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Vec<Box<Box<_>>> full scan");
+    let normal: Vec<Box<Box<i64>>> = (0..MEDIUM_NUM)
+        .map(|i| Box::new(Box::new(i as i64)))
+        .collect();
+    group.bench_function("Normal", |bencher| {
+        bencher.iter(|| black_box(&normal).iter().map(|b| ***b).sum::<i64>())
+    });
+    let pierce: Vec<Pierce<Box<Box<i64>>>> = normal.into_iter().map(Pierce::new).collect();
+    group.bench_function("Pierce", |bencher| {
+        bencher.iter(|| black_box(&pierce).iter().map(|p| **p).sum::<i64>())
+    });
+    let pierce_vec: PierceVec<Box<Box<i64>>> = pierce.into_iter().map(Pierce::into_outer).collect();
+    group.bench_function("PierceVec", |bencher| {
+        bencher.iter(|| black_box(&pierce_vec).iter().sum::<i64>())
+    });
+    group.finish();
+}
+
+fn fragmented_arc_string(c: &mut Criterion) {
+    let mut strings: Vec<Arc<String>> = (0..BIG_NUM)
+        .map(|idx| Arc::new((idx * idx).to_string()))
+        .collect();
+    let (l, r) = strings.split_at_mut(BIG_NUM / 2);
+    l.swap_with_slice(&mut r[..BIG_NUM / 2]);
+    let needle = (BIG_NUM - 1).pow(2).to_string();
+
+    let mut group = c.benchmark_group("Vec<Arc<String>>");
+    group.sample_size(20);
+    group.bench_function("Normal", |bencher| {
+        bencher.iter(|| strings.iter().position(|s| ***s == *black_box(&needle)))
+    });
+    let pierced: Vec<Pierce<Arc<String>>> = strings.into_iter().map(Pierce::new).collect();
+    group.bench_function("Pierce", |bencher| {
+        bencher.iter(|| pierced.iter().position(|s| **s == *black_box(&needle)))
+    });
+    group.finish();
+}
+
+// Look up long string keys repeatedly, hashing the whole string each time or not.
+fn hashed_long_keys(c: &mut Criterion) {
+    let keys: Vec<String> = (0..1000).map(|i| format!("{:0>1000}", i)).collect();
+    let build = RandomState::new();
+
+    let mut group = c.benchmark_group("HashSet<long keys>");
+    let plain: Vec<Pierce<Arc<String>>> = keys
+        .iter()
+        .map(|k| Pierce::new(Arc::new(k.clone())))
+        .collect();
+    let plain_set: HashSet<_> = plain.iter().cloned().collect();
+    group.bench_function("Pierce", |bencher| {
+        bencher.iter(|| {
+            plain
+                .iter()
+                .filter(|k| black_box(&plain_set).contains(*k))
+                .count()
+        })
+    });
+    let hashed: Vec<HashedPierce<Arc<String>>> = keys
+        .iter()
+        .map(|k| HashedPierce::new(Arc::new(k.clone()), &build))
+        .collect();
+    let hashed_set: HashSet<_> = hashed.iter().cloned().collect();
+    group.bench_function("HashedPierce", |bencher| {
+        bencher.iter(|| {
+            hashed
+                .iter()
+                .filter(|k| black_box(&hashed_set).contains(*k))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    fragmented_box_vec,
+    slow_box,
+    vec_box_box,
+    vec_box_box_scan,
+    fragmented_arc_string,
+    hashed_long_keys
+);
+criterion_main!(benches);
@@ -0,0 +1,210 @@
+//! Divan versions of the scenarios in `src/main.rs`, same as `benches/criterion.rs`.
+//!
+//! Run with `cargo bench --features divan --bench divan`.
+//! Allocations are counted too: the reads, with or without Pierce, should never allocate.
+
+// The nested boxes are the point of these benchmarks.
+#![allow(clippy::box_collection, clippy::vec_box)]
+
+use pierce::{HashedPierce, Pierce, PierceVec, StableDeref};
+use std::{
+    collections::{hash_map::RandomState, HashSet},
+    hint::black_box,
+    sync::Arc,
+};
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
+const SMALL_NUM: usize = 65536;
+const MEDIUM_NUM: usize = 1_000_000;
+const BIG_NUM: usize = 1_000_000;
+
+fn main() {
+    divan::main();
+}
+
+mod fragmented_box_vec {
+    use super::*;
+
+    fn setup() -> Box<Vec<usize>> {
+        let v: Vec<usize> = (0..SMALL_NUM).collect();
+        // Kinda simulate memory fragmentation by creating a lot of empty vecs.
+        let mut boxes: Vec<Box<Vec<usize>>> = (0..BIG_NUM).map(|_| Box::new(vec![])).collect();
+        *boxes[BIG_NUM / 2] = v;
+        std::mem::take(&mut boxes[BIG_NUM / 2])
+    }
+
+    #[divan::bench]
+    fn normal(bencher: divan::Bencher) {
+        let b = setup();
+        bencher.bench_local(|| {
+            let b = black_box(&b);
+            (0..SMALL_NUM).map(|i| b.get(i).unwrap()).sum::<usize>()
+        });
+    }
+
+    #[divan::bench]
+    fn pierce(bencher: divan::Bencher) {
+        let p = Pierce::new(setup());
+        bencher.bench_local(|| {
+            let p = black_box(&p);
+            (0..SMALL_NUM).map(|i| p.get(i).unwrap()).sum::<usize>()
+        });
+    }
+}
+
+mod slow_box {
+    use super::*;
+
+    // SlowBox: like Box but computes Collatz(31) every time you want to deref it.
+    struct SlowBox<T>(Box<T>);
+    impl<T> std::ops::Deref for SlowBox<T> {
+        type Target = T;
+        fn deref(&self) -> &Self::Target {
+            let mut n = black_box(31);
+            loop {
+                match n {
+                    1 => break self.0.deref(),
+                    _ if n % 2 == 0 => n /= 2,
+                    _ => n = n * 3 + 1,
+                }
+            }
+        }
+    }
+    unsafe impl<T> StableDeref for SlowBox<T> {}
+
+    fn setup() -> SlowBox<Vec<usize>> {
+        SlowBox(Box::new((0..SMALL_NUM).collect()))
+    }
+
+    #[divan::bench]
+    fn normal(bencher: divan::Bencher) {
+        let a = setup();
+        bencher.bench_local(|| (0..SMALL_NUM).map(|i| *a.get(i).unwrap()).sum::<usize>());
+    }
+
+    #[divan::bench]
+    fn pierce(bencher: divan::Bencher) {
+        let p = Pierce::new(setup());
+        bencher.bench_local(|| (0..SMALL_NUM).map(|i| *p.get(i).unwrap()).sum::<usize>());
+    }
+}
+
+mod vec_box_box {
+    use super::*;
+
+    fn setup() -> Vec<Box<Box<i64>>> {
+        (0..MEDIUM_NUM)
+            .map(|i| Box::new(Box::new(i as i64)))
+            .collect()
+    }
+
+    // Jump around the vec following the Collatz sequence of 65535.
+    #[inline]
+    fn collatz_sum(get: impl Fn(usize) -> i64) -> i64 {
+        let mut sum = 0;
+        let mut i: usize = 65535;
+        loop {
+            match i {
+                1 => break sum,
+                v if v % 2 == 1 => i = v * 3 + 1,
+                v => i = v / 2,
+            }
+            sum += get(i % MEDIUM_NUM);
+        }
+    }
+
+    #[divan::bench]
+    fn normal(bencher: divan::Bencher) {
+        let v = setup();
+        bencher.bench_local(|| collatz_sum(|i| ***black_box(&v).get(i).unwrap()));
+    }
+
+    #[divan::bench]
+    fn pierce(bencher: divan::Bencher) {
+        let v: Vec<Pierce<Box<Box<i64>>>> = setup().into_iter().map(Pierce::new).collect();
+        bencher.bench_local(|| collatz_sum(|i| **black_box(&v).get(i).unwrap()));
+    }
+
+    #[divan::bench]
+    fn pierce_vec(bencher: divan::Bencher) {
+        let v: PierceVec<Box<Box<i64>>> = setup().into_iter().collect();
+        bencher.bench_local(|| collatz_sum(|i| *black_box(&v).get(i).unwrap()));
+    }
+
+    // Sum every item in order. PierceVec keeps its cached pointers in their own array, so only that is read.
+    #[divan::bench]
+    fn scan_normal(bencher: divan::Bencher) {
+        let v = setup();
+        bencher.bench_local(|| black_box(&v).iter().map(|b| ***b).sum::<i64>());
+    }
+
+    #[divan::bench]
+    fn scan_pierce(bencher: divan::Bencher) {
+        let v: Vec<Pierce<Box<Box<i64>>>> = setup().into_iter().map(Pierce::new).collect();
+        bencher.bench_local(|| black_box(&v).iter().map(|p| **p).sum::<i64>());
+    }
+
+    #[divan::bench]
+    fn scan_pierce_vec(bencher: divan::Bencher) {
+        let v: PierceVec<Box<Box<i64>>> = setup().into_iter().collect();
+        bencher.bench_local(|| black_box(&v).iter().sum::<i64>());
+    }
+}
+
+mod fragmented_arc_string {
+    use super::*;
+
+    fn setup() -> (Vec<Arc<String>>, String) {
+        let mut strings: Vec<Arc<String>> = (0..BIG_NUM)
+            .map(|idx| Arc::new((idx * idx).to_string()))
+            .collect();
+        let (l, r) = strings.split_at_mut(BIG_NUM / 2);
+        l.swap_with_slice(&mut r[..BIG_NUM / 2]);
+        (strings, (BIG_NUM - 1).pow(2).to_string())
+    }
+
+    #[divan::bench(sample_count = 20)]
+    fn normal(bencher: divan::Bencher) {
+        let (strings, needle) = setup();
+        bencher.bench_local(|| strings.iter().position(|s| ***s == *black_box(&needle)));
+    }
+
+    #[divan::bench(sample_count = 20)]
+    fn pierce(bencher: divan::Bencher) {
+        let (strings, needle) = setup();
+        let pierced: Vec<Pierce<Arc<String>>> = strings.into_iter().map(Pierce::new).collect();
+        bencher.bench_local(|| pierced.iter().position(|s| **s == *black_box(&needle)));
+    }
+}
+
+mod hashed_long_keys {
+    use super::*;
+
+    // Look up long string keys repeatedly, hashing the whole string each time or not.
+    fn setup() -> Vec<String> {
+        (0..1000).map(|i| format!("{:0>1000}", i)).collect()
+    }
+
+    #[divan::bench]
+    fn pierce(bencher: divan::Bencher) {
+        let keys: Vec<Pierce<Arc<String>>> = setup()
+            .into_iter()
+            .map(|k| Pierce::new(Arc::new(k)))
+            .collect();
+        let set: HashSet<_> = keys.iter().cloned().collect();
+        bencher.bench_local(|| keys.iter().filter(|k| black_box(&set).contains(*k)).count());
+    }
+
+    #[divan::bench]
+    fn hashed_pierce(bencher: divan::Bencher) {
+        let build = RandomState::new();
+        let keys: Vec<HashedPierce<Arc<String>>> = setup()
+            .into_iter()
+            .map(|k| HashedPierce::new(Arc::new(k), &build))
+            .collect();
+        let set: HashSet<_> = keys.iter().cloned().collect();
+        bencher.bench_local(|| keys.iter().filter(|k| black_box(&set).contains(*k)).count());
+    }
+}
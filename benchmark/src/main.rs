@@ -1,5 +1,8 @@
-use pierce::{Pierce, StableDeref};
-use std::time::{Duration, Instant};
+use pierce::{Pierce, PierceVec, StableDeref};
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
 const SMALL_NUM: usize = 65536;
 const MEDIUM_NUM: usize = 1_000_000;
@@ -16,7 +19,7 @@ fn bench_fragmented_box_vec() {
         // Confuse the optimizer and kinda simulate memory fragmentation by creating a lot of empty vecs.
         let mut boxes: Vec<Box<Vec<usize>>> = (0..BIG_NUM).map(|_| Box::new(vec![])).collect();
         *boxes[BIG_NUM / 2] = v;
-        let b = std::mem::replace(&mut boxes[BIG_NUM / 2], Default::default());
+        let b = std::mem::take(&mut boxes[BIG_NUM / 2]);
 
         let mut _sum = 0;
 
@@ -35,7 +38,7 @@ fn bench_fragmented_box_vec() {
 
         let mut boxes: Vec<Box<Vec<usize>>> = (0..BIG_NUM).map(|_| Box::new(vec![])).collect();
         *boxes[BIG_NUM / 2] = v;
-        let b = std::mem::replace(&mut boxes[BIG_NUM / 2], Default::default());
+        let b = std::mem::take(&mut boxes[BIG_NUM / 2]);
 
         let mut _sum = 0;
         let start = Instant::now();
@@ -172,23 +175,51 @@ fn bench_vec_box_box() {
         assert!(sum > 4000i64);
         start.elapsed()
     }
+    #[inline(never)]
+    fn pierce_vec() -> Duration {
+        let start = Instant::now();
+        let v: PierceVec<Box<Box<i64>>> = (0..MEDIUM_NUM)
+            .map(|i| Box::new(Box::new(i as i64)))
+            .collect();
+        let mut sum = 0i64;
+        for _ in 0..MEDIUM_NUM {
+            let mut i: usize = 65535;
+            loop {
+                match i {
+                    1 => break,
+                    v if v % 2 == 1 => i = v * 3 + 1,
+                    v => i = v / 2,
+                }
+                sum += *v.get(i % MEDIUM_NUM).unwrap();
+            }
+        }
+        assert!(sum > 4000i64);
+        start.elapsed()
+    }
 
     let mut normal_took = Duration::from_secs(0);
     let mut pierce_took = Duration::from_secs(0);
+    let mut pierce_vec_took = Duration::from_secs(0);
 
     println!("Vec<Box<Box<_>>> benchmark");
 
     // Warm up a bit.
     normal();
     pierce();
+    pierce_vec();
 
     // Actual runs.
     normal_took += normal();
     pierce_took += pierce();
+    pierce_vec_took += pierce_vec();
     normal_took += normal();
     pierce_took += pierce();
+    pierce_vec_took += pierce_vec();
 
-    println!("Normal: {:.2?}, Pierce: {:.2?}", normal_took, pierce_took);
+    println!(
+        "Normal: {:.2?}, Pierce: {:.2?}, PierceVec: {:.2?}",
+        normal_took, pierce_took, pierce_vec_took
+    );
 }
 
 #[inline(never)]
@@ -232,7 +263,7 @@ fn bench_fragmented_arc_string() {
         let u = t.to_string();
         let start = Instant::now();
         for (idx, s) in strings.iter().enumerate() {
-            if (*s).partial_cmp(&u) == Some(std::cmp::Ordering::Equal) {
+            if s.partial_cmp(&u) == Some(std::cmp::Ordering::Equal) {
                 assert_eq!(idx, 14620135);
                 break;
             }
@@ -257,9 +288,61 @@ fn bench_fragmented_arc_string() {
     println!("Normal: {:.2?}, Pierce: {:.2?}", normal_took, pierce_took);
 }
 
+#[inline(never)]
+fn bench_rwlock_vec() {
+    #[inline(never)]
+    fn relock() -> Duration {
+        let lock = Arc::new(RwLock::new((0..SMALL_NUM as u64).collect::<Vec<_>>()));
+        let start = Instant::now();
+        let mut sum = 0u64;
+        for _ in 0..1000 {
+            for i in 0..SMALL_NUM {
+                sum = sum.wrapping_add(std::hint::black_box(&lock).read().unwrap()[i]);
+            }
+        }
+        std::hint::black_box(sum);
+        start.elapsed()
+    }
+
+    #[inline(never)]
+    fn pierce() -> Duration {
+        let lock = Arc::new(RwLock::new((0..SMALL_NUM as u64).collect::<Vec<_>>()));
+        let start = Instant::now();
+        let mut sum = 0u64;
+        for _ in 0..1000 {
+            let guard = pierce::lock::read(&lock).unwrap();
+            for i in 0..SMALL_NUM {
+                sum = sum.wrapping_add(std::hint::black_box(&guard)[i]);
+            }
+        }
+        std::hint::black_box(sum);
+        start.elapsed()
+    }
+    let mut relock_took = Duration::from_secs(0);
+    let mut pierce_took = Duration::from_secs(0);
+
+    println!("Arc<RwLock<Vec<_>>> benchmark");
+
+    // Warm up a bit.
+    relock();
+    pierce();
+
+    // Actual runs.
+    relock_took += relock();
+    pierce_took += pierce();
+    relock_took += relock();
+    pierce_took += pierce();
+
+    println!(
+        "Re-locking: {:.2?}, Pierce: {:.2?}",
+        relock_took, pierce_took
+    );
+}
+
 fn main() {
     bench_fragmented_box_vec();
     bench_slow_box();
     bench_vec_box_box();
     bench_fragmented_arc_string();
+    bench_rwlock_vec();
 }
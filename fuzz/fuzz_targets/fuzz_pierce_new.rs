@@ -0,0 +1,58 @@
+//! Check that a fresh Pierce derefs to exactly what deref-ing the outer pointer twice gives.
+//!
+//! The first byte picks the nested pointer type and the rest is the data.
+//! Types cover zero-sized targets, empty and large targets,
+//! and targets that sit at the very start of the inner pointer's allocation.
+//!
+//! Run with `cargo +nightly fuzz run fuzz_pierce_new` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pierce::Pierce;
+use std::{ops::Deref, rc::Rc, sync::Arc};
+
+fn check<T>(outer: T)
+where
+    T: pierce::StableDeref,
+    T::Target: pierce::StableDeref,
+    <T::Target as Deref>::Target: PartialEq,
+{
+    let expected: *const <T::Target as Deref>::Target = outer.deref().deref();
+    let pierce = Pierce::new(outer);
+    let cached: *const <T::Target as Deref>::Target = &*pierce;
+    assert!(std::ptr::eq(cached, expected));
+    let outer: &T = pierce.borrow_outer();
+    assert!(*pierce == ***outer);
+    // Moving the Pierce must not invalidate the cache.
+    let moved = Box::new(pierce);
+    assert!(std::ptr::eq(&**moved, expected));
+}
+
+fuzz_target!(|data: &[u8]| {
+    let (&selector, rest) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    match selector % 8 {
+        0 => check(Box::new(Box::new(rest.first().copied().unwrap_or(0)))),
+        // Zero-sized target: the pointer is dangling but must still round-trip.
+        1 => check(Box::new(Box::new(()))),
+        2 => check(Box::new(rest.to_vec())),
+        3 => check(Rc::new(rest.to_vec().into_boxed_slice())),
+        4 => check(Arc::new(String::from_utf8_lossy(rest).into_owned())),
+        5 => {
+            let mut big = Box::new([0u8; 4096]);
+            big[..rest.len().min(4096)].copy_from_slice(&rest[..rest.len().min(4096)]);
+            check(Box::new(big));
+        }
+        // Target at the start of the inner allocation: the first element of a Vec with spare capacity.
+        6 => {
+            let mut v = Vec::with_capacity(rest.len() * 2 + 1);
+            v.extend_from_slice(rest);
+            check(Arc::new(v));
+        }
+        // Zero-sized elements: a non-empty slice with no bytes.
+        _ => check(Box::new(vec![(); rest.len()])),
+    }
+});
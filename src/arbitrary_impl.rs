@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{OptionPierce, Pierce, PierceVec, StableDeref};
+
+/* Generate the outer pointers, then cache them.
+The bytes are consumed exactly like generating the outer pointers would, so the size hints are theirs too. */
+impl<'a, T> Arbitrary<'a> for Pierce<T>
+where
+    T: StableDeref + Arbitrary<'a>,
+    T::Target: StableDeref,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        T::arbitrary(u).map(Self::new)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        T::arbitrary_take_rest(u).map(Self::new)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        T::size_hint(depth)
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for OptionPierce<T>
+where
+    T: StableDeref + Arbitrary<'a>,
+    T::Target: StableDeref,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Option::arbitrary(u).map(Self::new)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        Option::arbitrary_take_rest(u).map(Self::new)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <Option<T>>::size_hint(depth)
+    }
+}
+
+impl<'a, T> Arbitrary<'a> for PierceVec<T>
+where
+    T: StableDeref + Arbitrary<'a>,
+    T::Target: StableDeref,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Vec::arbitrary(u).map(Self::from)
+    }
+
+    fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+        Vec::arbitrary_take_rest(u).map(Self::from)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <Vec<T>>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pierced;
+    use std::sync::Arc;
+
+    const BYTES: &[u8] =
+        b"\x03some fixed fuzzer input, long enough for a few fields\x01\x02\x03\x04";
+
+    #[derive(Arbitrary)]
+    #[allow(clippy::box_collection, clippy::redundant_allocation)]
+    struct Config {
+        name: Pierce<Arc<String>>,
+        payload: Pierce<Box<Vec<u8>>>,
+        fallback: OptionPierce<Box<Vec<u8>>>,
+        extra: PierceVec<Box<Box<u16>>>,
+    }
+
+    #[derive(Arbitrary)]
+    #[allow(clippy::box_collection, clippy::vec_box)]
+    struct PlainConfig {
+        name: Arc<String>,
+        payload: Box<Vec<u8>>,
+        fallback: Option<Box<Vec<u8>>>,
+        extra: Vec<Box<Box<u16>>>,
+    }
+
+    #[test]
+    fn test_derive_matches_plain() {
+        let config = Config::arbitrary(&mut Unstructured::new(BYTES)).unwrap();
+        let plain = PlainConfig::arbitrary(&mut Unstructured::new(BYTES)).unwrap();
+        assert!(config.name.is_cached() && config.name.is_cache_valid());
+        assert!(config.payload.is_cached() && config.payload.is_cache_valid());
+        assert_eq!(config.name, **plain.name);
+        assert_eq!(config.payload, plain.payload[..]);
+        assert_eq!(
+            config.fallback.as_deref(),
+            plain.fallback.as_deref().map(|v| &v[..])
+        );
+        assert!(config.extra.iter().eq(plain.extra.iter().map(|b| &***b)));
+
+        let config = Config::arbitrary_take_rest(Unstructured::new(BYTES)).unwrap();
+        let plain = PlainConfig::arbitrary_take_rest(Unstructured::new(BYTES)).unwrap();
+        assert_eq!(config.payload, plain.payload[..]);
+        assert!(config.extra.iter().eq(plain.extra.iter().map(|b| &***b)));
+    }
+
+    #[test]
+    fn test_size_hint() {
+        assert_eq!(
+            <Pierce<Box<Box<u32>>>>::size_hint(0),
+            <Box<Box<u32>>>::size_hint(0)
+        );
+        assert_eq!(<Pierce<Box<Box<u32>>>>::size_hint(0), (4, Some(4)));
+        assert_eq!(
+            <OptionPierce<Box<Box<u32>>>>::size_hint(0),
+            <Option<Box<Box<u32>>>>::size_hint(0)
+        );
+        assert_eq!(
+            <PierceVec<Box<Box<u32>>>>::size_hint(0),
+            <Vec<Box<Box<u32>>>>::size_hint(0)
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    fmt,
+    marker::PhantomData,
+    ops::Deref,
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicPtr, AtomicUsize, Ordering},
+        Mutex, PoisonError,
+    },
+};
+
+use crate::StableDeref;
+
+/** A cache of a nested pointer that can be re-pointed through a shared reference.
+
+`AtomicPierce<T>` is like a [`Pierce`][crate::Pierce] whose outer pointer can be replaced with [`store`][Self::store]
+while other threads keep reading through [`load`][Self::load].
+The cached target pointer lives in an [`AtomicPtr`], so a load is an atomic increment and an atomic read,
+and each access through the returned guard is one jump.
+
+```
+# use std::sync::Arc;
+# use pierce::AtomicPierce;
+let routes = AtomicPierce::new(Arc::new(Box::new([1, 2, 3])));
+std::thread::scope(|s| {
+    s.spawn(|| assert!(routes.load()[0] >= 1));
+    routes.store(Arc::new(Box::new([4, 5, 6])));
+});
+assert_eq!(*routes.load(), [4, 5, 6]);
+```
+
+# Reclamation
+
+[`load`][Self::load] returns an [`AtomicPierceGuard`], and the AtomicPierce counts the guards that are alive.
+A replaced outer pointer can't always be dropped in `store`: a guard on another thread might still be looking at its target.
+Instead, it is retired into a list, and retired outer pointers are dropped as soon as no guard is alive:
+by the `store` itself if there are none, or else by the last guard to be dropped.
+So retired outer pointers only pile up while some guard is always alive.
+[`reclaim`][Self::reclaim] drops them unconditionally, since with `&mut self` no guard can exist.
+
+Keep guards short-lived, like lock guards. Holding one across stores keeps every outer pointer stored meanwhile alive.
+
+There is no `refresh`. Re-deref-ing the current outer pointer would always give the same address,
+because `StableDeref` guarantees its target doesn't move. To re-point the cache, `store` a new outer pointer.
+
+Only `Sized` targets are supported, since [`AtomicPtr`] can't hold fat pointers.
+*/
+pub struct AtomicPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    target: AtomicPtr<<T::Target as Deref>::Target>,
+    // The number of guards alive.
+    readers: AtomicUsize,
+    // The number of retired outer pointers in `outers`. Only changed with `outers` locked.
+    retired: AtomicUsize,
+    // The current outer pointer is the last one. The others are retired.
+    outers: Mutex<Vec<T>>,
+    _target: PhantomData<*const <T::Target as Deref>::Target>,
+}
+
+impl<T> AtomicPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    /** Create a new AtomicPierce.
+
+    This method derefs `T` twice and cache the address where the inner pointer points to,
+    just like [`Pierce::new`][crate::Pierce::new].
+     */
+    #[inline]
+    pub fn new(outer: T) -> Self {
+        let target = Self::target_of(&outer);
+        Self {
+            target: AtomicPtr::new(target),
+            readers: AtomicUsize::new(0),
+            retired: AtomicUsize::new(0),
+            outers: Mutex::new(vec![outer]),
+            _target: PhantomData,
+        }
+    }
+
+    #[inline]
+    fn target_of(outer: &T) -> *mut <T::Target as Deref>::Target {
+        let target: &<T::Target as Deref>::Target = outer.deref().deref();
+        target as *const _ as *mut _
+    }
+
+    /** Get the currently cached target.
+
+    The outer pointer it came from is kept alive until the returned guard is dropped,
+    even if another one is stored meanwhile. See [Reclamation](#reclamation).
+     */
+    #[inline]
+    pub fn load(&self) -> AtomicPierceGuard<'_, T> {
+        self.readers.fetch_add(1, Ordering::SeqCst);
+        let target = self.target.load(Ordering::SeqCst);
+        AtomicPierceGuard {
+            pierce: self,
+            target: unsafe { NonNull::new_unchecked(target) },
+            // SAFETY: The pointer was made from a reference.
+        }
+    }
+
+    /** Replace the outer pointer, caching the new target.
+
+    The old outer pointer is retired, and dropped right away if no guard is alive. See [Reclamation](#reclamation).
+     */
+    pub fn store(&self, outer: T) {
+        let target = Self::target_of(&outer);
+        let mut outers = self.outers.lock().unwrap_or_else(PoisonError::into_inner);
+        outers.push(outer);
+        self.target.store(target, Ordering::SeqCst);
+        self.retired.fetch_add(1, Ordering::SeqCst);
+        self.reclaim_unread(&mut outers);
+    }
+
+    /* Drop the retired outer pointers if no guard is alive.
+    `outers` must be locked, so the target can't be replaced while this runs.
+
+    A guard that can see a retired target loaded it before the store that retired it.
+    It counted itself in `readers` before that, and all of these are SeqCst,
+    so if it is still alive, the load of `readers` here sees it.
+    A guard counted after the load of `readers` here loads the current target, which isn't dropped. */
+    fn reclaim_unread(&self, outers: &mut Vec<T>) {
+        if self.readers.load(Ordering::SeqCst) == 0 {
+            Self::keep_current(outers);
+            self.retired.store(0, Ordering::SeqCst);
+        }
+    }
+
+    fn keep_current(outers: &mut Vec<T>) {
+        let current = outers.pop();
+        outers.clear();
+        outers.extend(current);
+    }
+
+    /** Drop all retired outer pointers, keeping only the current one.
+
+    Retired outer pointers are dropped without this once no guard is alive.
+    This is only needed if guards are held all the time.
+     */
+    pub fn reclaim(&mut self) {
+        Self::keep_current(
+            self.outers
+                .get_mut()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        *self.retired.get_mut() = 0;
+    }
+
+    /** The number of retired outer pointers that are not dropped yet.
+     */
+    pub fn retired_count(&self) -> usize {
+        self.retired.load(Ordering::SeqCst)
+    }
+
+    /** Get the current outer pointer `T` out. Retired outer pointers are dropped.
+     */
+    pub fn into_outer(self) -> T {
+        let mut outers = self
+            .outers
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        outers.pop().unwrap()
+    }
+}
+
+unsafe impl<T> Send for AtomicPierce<T>
+where
+    T: StableDeref + Send,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized + Sync,
+{
+}
+
+/* Sharing an AtomicPierce lets any thread load the target and store (and thus later drop) outer pointers.
+So we need the target to be Sync and T to be Send. T is never accessed through a shared reference. */
+unsafe impl<T> Sync for AtomicPierce<T>
+where
+    T: StableDeref + Send,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized + Sync,
+{
+}
+
+/** The target of an [`AtomicPierce`], as loaded by [`load`][AtomicPierce::load].
+
+While it is alive, the outer pointer the target came from is not dropped.
+*/
+pub struct AtomicPierceGuard<'a, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    pierce: &'a AtomicPierce<T>,
+    target: NonNull<<T::Target as Deref>::Target>,
+}
+
+impl<T> Deref for AtomicPierceGuard<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    type Target = <T::Target as Deref>::Target;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.target.as_ref() }
+        /* SAFETY:
+        The pointer was derived from an outer pointer that is in `outers`,
+        and this guard is counted in `readers` since before the pointer was loaded.
+        Retired outer pointers are only dropped when `readers` is zero, or with `&mut` access to the AtomicPierce,
+        which can't happen while this guard borrows it.
+
+        The SeqCst load of the target pairs with the SeqCst store in `store`,
+        so the target the pointer points to is visible to us.
+
+        See `Cached::get` for why the target stays at the same place while the outer pointer is alive.
+        */
+    }
+}
+
+impl<T> Drop for AtomicPierceGuard<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    fn drop(&mut self) {
+        let pierce = self.pierce;
+        // The last guard to go drops what was retired while guards were alive.
+        // A `store` racing with this sees either this guard gone or the retired count raised, so nothing is left behind.
+        if pierce.readers.fetch_sub(1, Ordering::SeqCst) == 1
+            && pierce.retired.load(Ordering::SeqCst) != 0
+        {
+            let mut outers = pierce.outers.lock().unwrap_or_else(PoisonError::into_inner);
+            pierce.reclaim_unread(&mut outers);
+        }
+    }
+}
+
+impl<T> fmt::Debug for AtomicPierceGuard<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_store_and_reclaim() {
+        let first = Arc::new(Box::new(1u64));
+        let mut ap = AtomicPierce::new(first.clone());
+        assert_eq!(&*ap.load() as *const u64, &**first as *const u64);
+        let guard = ap.load();
+        ap.store(Arc::new(Box::new(2)));
+        ap.store(Arc::new(Box::new(3)));
+        assert_eq!(*guard, 1);
+        assert_eq!(*ap.load(), 3);
+        assert_eq!(ap.retired_count(), 2);
+        assert_eq!(Arc::strong_count(&first), 2);
+        drop(guard);
+        assert_eq!(ap.retired_count(), 0);
+        assert_eq!(Arc::strong_count(&first), 1);
+
+        // Without guards, stores drop the old outer pointer right away.
+        ap.store(first.clone());
+        assert_eq!(ap.retired_count(), 0);
+        let guard = ap.load();
+        ap.store(Arc::new(Box::new(4)));
+        assert_eq!(Arc::strong_count(&first), 2);
+        drop(guard);
+        assert_eq!(Arc::strong_count(&first), 1);
+
+        let guard = ap.load();
+        ap.store(Arc::new(Box::new(5)));
+        std::mem::forget(guard);
+        assert_eq!(ap.retired_count(), 1);
+        ap.reclaim();
+        assert_eq!(ap.retired_count(), 0);
+        assert_eq!(*ap.load(), 5);
+        assert_eq!(**ap.into_outer(), 5);
+    }
+
+    #[test]
+    fn test_concurrent_readers() {
+        const WRITES: u64 = 1000;
+        let ap = AtomicPierce::new(Arc::new(Box::new([0u64; 8])));
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    let mut last = 0;
+                    while last < WRITES {
+                        let loaded = ap.load();
+                        let value = loaded[0];
+                        // Every slot of one array was written together. A torn or freed array would differ.
+                        assert!(loaded.iter().all(|v| *v == value));
+                        assert!(value >= last);
+                        last = value;
+                    }
+                });
+            }
+            s.spawn(|| {
+                for i in 1..=WRITES {
+                    ap.store(Arc::new(Box::new([i; 8])));
+                }
+            });
+        });
+        assert_eq!(*ap.load(), [WRITES; 8]);
+        // Everything retired was dropped once the readers were done, without `reclaim`.
+        assert_eq!(ap.retired_count(), 0);
+    }
+
+    #[test]
+    fn test_retired_dropped_without_mut() {
+        // A long-lived AtomicPierce shared by reference, which never gets `&mut`.
+        let first = Arc::new(Box::new(0u64));
+        let ap = AtomicPierce::new(first.clone());
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..10_000 {
+                        let guard = ap.load();
+                        assert!(*guard <= 10_000);
+                    }
+                });
+            }
+            for i in 1..=10_000 {
+                ap.store(Arc::new(Box::new(i)));
+            }
+        });
+        assert_eq!(ap.retired_count(), 0);
+        assert_eq!(Arc::strong_count(&first), 1);
+    }
+}
@@ -0,0 +1,130 @@
+use core::ops::Deref;
+
+use bincode::{
+    de::{BorrowDecode, BorrowDecoder, Decode, Decoder},
+    enc::{Encode, Encoder},
+    error::{DecodeError, EncodeError},
+};
+
+use crate::{Pierce, StableDeref};
+
+/** Encode a Pierce as its target.
+
+Like the serde impl, a `Pierce<Box<Vec<u8>>>` encodes to the same bytes as the `[u8]` would.
+*/
+impl<T> Encode for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Encode,
+{
+    #[inline]
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        Encode::encode(&**self, encoder)
+    }
+}
+
+/** Decode the outer pointer, then cache its target.
+
+bincode encodes `Box`, `Rc` and `Arc` as what they point to, so this reads what the `Encode` impl writes.
+*/
+impl<T, C> Decode<C> for Pierce<T>
+where
+    T: StableDeref + Decode<C>,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn decode<D: Decoder<Context = C>>(decoder: &mut D) -> Result<Self, DecodeError> {
+        T::decode(decoder).map(Pierce::new)
+    }
+}
+
+impl<'de, T, C> BorrowDecode<'de, C> for Pierce<T>
+where
+    T: StableDeref + BorrowDecode<'de, C>,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn borrow_decode<D: BorrowDecoder<'de, Context = C>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        T::borrow_decode(decoder).map(Pierce::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bincode::config::standard;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    fn encode<V: Encode + ?Sized>(value: &V) -> Vec<u8> {
+        bincode::encode_to_vec(value, standard()).unwrap()
+    }
+
+    fn decode<V: Decode<()>>(bytes: &[u8]) -> V {
+        let (value, read) = bincode::decode_from_slice(bytes, standard()).unwrap();
+        assert_eq!(read, bytes.len());
+        value
+    }
+
+    #[test]
+    fn test_round_trip_vec() {
+        let values = vec![0u64, 1, 300, u64::MAX];
+        let bytes = encode(&values);
+        let pierce = Pierce::new(Arc::new(values.clone()));
+        assert_eq!(encode(&pierce), bytes);
+        let back: Pierce<Arc<Vec<u64>>> = decode(&bytes);
+        assert_eq!(back, values[..]);
+        assert!(back.is_cache_valid());
+    }
+
+    #[test]
+    fn test_round_trip_string() {
+        let bytes = encode("hello");
+        assert_eq!(encode(&Pierce::new(Rc::new(String::from("hello")))), bytes);
+        let back: Pierce<Box<String>> = decode(&bytes);
+        assert_eq!(back, "hello");
+        let borrowed: (Pierce<Box<Box<&str>>>, usize) =
+            bincode::borrow_decode_from_slice(&bytes, standard()).unwrap();
+        assert_eq!(*borrowed.0, "hello");
+    }
+
+    #[test]
+    #[allow(clippy::redundant_allocation)]
+    fn test_round_trip_struct() {
+        #[derive(bincode::Encode, bincode::Decode, PartialEq, Debug)]
+        struct Record {
+            id: u32,
+            tags: Vec<String>,
+            parent: Option<Box<Record>>,
+        }
+
+        #[derive(bincode::Encode, bincode::Decode)]
+        struct Stored {
+            name: Pierce<Arc<String>>,
+            record: Pierce<Box<Box<Record>>>,
+        }
+
+        let record = || Record {
+            id: 7,
+            tags: vec![String::from("a"), String::from("b")],
+            parent: Some(Box::new(Record {
+                id: 1,
+                tags: vec![],
+                parent: None,
+            })),
+        };
+        let stored = Stored {
+            name: Pierce::new(Arc::new(String::from("r"))),
+            record: Pierce::new(Box::new(Box::new(record()))),
+        };
+        let bytes = encode(&stored);
+        assert_eq!(bytes, encode(&("r", record())));
+
+        let back: Stored = decode(&bytes);
+        assert_eq!(back.name, "r");
+        assert_eq!(*back.record, record());
+    }
+}
@@ -0,0 +1,123 @@
+use core::ops::Deref;
+
+use crate::StableDeref;
+
+/** A cached view of a nested pointer that you only have a reference to.
+
+`PierceBorrowed<'a, T>` holds `&'a T` and a reference to its cached target.
+Unlike `Pierce<&'a T>`, there's no extra level of reference to go through, and it's just two references,
+so it is `Copy`.
+
+```
+# use std::sync::Arc;
+# use pierce::PierceBorrowed;
+struct Config {
+    names: Arc<Vec<String>>,
+}
+let config = Config { names: Arc::new(vec![String::from("a"), String::from("b")]) };
+let names = PierceBorrowed::new(&config.names);
+let copied = names;
+assert_eq!(names.len(), 2);
+assert_eq!(copied[1], "b");
+```
+*/
+pub struct PierceBorrowed<'a, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    outer: &'a T,
+    target: &'a <T::Target as Deref>::Target,
+}
+
+impl<'a, T> PierceBorrowed<'a, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    /** Create a new PierceBorrowed.
+
+    This method derefs `T` twice and cache the address where the inner pointer points to,
+    just like [`Pierce::new`][crate::Pierce::new].
+     */
+    #[inline]
+    pub fn new(outer: &'a T) -> Self {
+        Self {
+            outer,
+            target: outer.deref().deref(),
+        }
+    }
+
+    /** Get the borrowed outer pointer `T`.
+     */
+    #[inline]
+    pub fn outer(&self) -> &'a T {
+        self.outer
+    }
+
+    /** Get the cached target, with the full lifetime of the borrow.
+     */
+    #[inline]
+    pub fn target(&self) -> &'a <T::Target as Deref>::Target {
+        self.target
+    }
+}
+
+impl<T> Clone for PierceBorrowed<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PierceBorrowed<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+}
+
+impl<T> Deref for PierceBorrowed<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    type Target = <T::Target as Deref>::Target;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.target
+    }
+}
+
+impl<'a, T> From<&'a T> for PierceBorrowed<'a, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn from(outer: &'a T) -> Self {
+        Self::new(outer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::Cell, sync::Arc};
+
+    #[test]
+    fn test_copies_share_target() {
+        let arc = Arc::new(vec![1, 2, 3]);
+        let borrowed = PierceBorrowed::new(&arc);
+        let cell = Cell::new(borrowed);
+        let copied = cell.get();
+        assert_eq!(&*copied as *const [i32], &**arc as *const [i32]);
+        assert_eq!(&*borrowed as *const [i32], &*copied as *const [i32]);
+        assert!(core::ptr::eq(copied.outer(), &arc));
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+}
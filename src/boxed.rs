@@ -0,0 +1,121 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::iter::FromIterator;
+
+use crate::{Pierce, StableDeref};
+
+impl<U> Pierce<Box<U>>
+where
+    U: StableDeref,
+{
+    /** Unbox the inner pointer and get it out.
+
+    This is `*pierce.into_outer()`. Nothing is cloned.
+    ```
+    # use pierce::Pierce;
+    let pierce = Pierce::new(Box::new(String::from("hello")));
+    let bytes: Vec<u8> = pierce.into_inner().into_bytes();
+    assert_eq!(bytes, b"hello");
+    ```
+     */
+    #[inline]
+    pub fn into_inner(self) -> U {
+        *self.outer
+    }
+}
+
+/** Iterate over the inner pointer by value, after unboxing it.
+
+This works when the inner pointer is `IntoIterator` by value, like `Vec<T>` and `Box<[T]>`, and yields owned items.
+Strings aren't `IntoIterator`; use [`into_inner`][Pierce::into_inner] and then [`String::into_bytes`] instead.
+
+Shared outer pointers like `Rc` and `Arc` can't be unwrapped unconditionally.
+Get the outer pointer with [`into_outer`][Pierce::into_outer] and try [`Rc::try_unwrap`][alloc::rc::Rc::try_unwrap] on it.
+```
+# use pierce::Pierce;
+struct Token(u32);
+let pierce = Pierce::new(Box::new(vec![Token(1), Token(2)]));
+let owned: Vec<Token> = pierce.into_iter().collect();
+assert_eq!(owned[1].0, 2);
+```
+*/
+impl<U> IntoIterator for Pierce<Box<U>>
+where
+    U: StableDeref + IntoIterator,
+{
+    type Item = U::Item;
+    type IntoIter = U::IntoIter;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_inner().into_iter()
+    }
+}
+
+/** Collect into a boxed `Vec`, and cache its slice.
+
+The name `PierceVec` is taken by [`PierceVec`][crate::PierceVec], a collection of many Pierces,
+so this is spelled out as `Pierce<Box<Vec<U>>>`.
+```
+# use pierce::Pierce;
+let squares: Pierce<Box<Vec<u32>>> = (1..=4).map(|x| x * x).collect();
+assert_eq!(squares, [1, 4, 9, 16]);
+assert_eq!(squares.iter().sum::<u32>(), 30);
+```
+*/
+impl<U> FromIterator<U> for Pierce<Box<Vec<U>>> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = U>>(iter: I) -> Self {
+        Pierce::new(Box::new(iter.into_iter().collect()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Not Clone, so anything yielded must have been moved out.
+    #[derive(Debug, PartialEq)]
+    struct NoClone(u32);
+
+    #[test]
+    fn test_into_iter_vec() {
+        let pierce = Pierce::new(Box::new(vec![NoClone(1), NoClone(2), NoClone(3)]));
+        let first: *const NoClone = &pierce[0];
+        let mut iter = pierce.into_iter();
+        assert_eq!(iter.as_slice().as_ptr(), first);
+        assert_eq!(iter.next(), Some(NoClone(1)));
+        let mut sum = 0;
+        for NoClone(x) in iter {
+            sum += x;
+        }
+        assert_eq!(sum, 5);
+    }
+
+    #[test]
+    fn test_into_iter_boxed_slice() {
+        let pierce = Pierce::new(Box::new(Box::<[NoClone]>::from([NoClone(7)])));
+        let owned: Vec<NoClone> = pierce.into_iter().collect();
+        assert_eq!(owned, [NoClone(7)]);
+    }
+
+    #[test]
+    fn test_from_iter() {
+        let pierce: Pierce<Box<Vec<NoClone>>> = (0..3).map(NoClone).collect();
+        assert_eq!(pierce.len(), 3);
+        assert_eq!(pierce[2], NoClone(2));
+        assert_eq!(pierce.as_ptr(), pierce.borrow_outer().as_ptr());
+        let empty: Pierce<Box<Vec<u8>>> = core::iter::empty().collect();
+        assert!(empty.is_empty());
+        // Collecting and iterating by value round-trips.
+        let back: Vec<NoClone> = pierce.into_iter().collect();
+        assert_eq!(back, [NoClone(0), NoClone(1), NoClone(2)]);
+    }
+
+    #[test]
+    fn test_into_inner_string() {
+        let pierce = Pierce::new(Box::new(String::from("hé")));
+        let ptr = pierce.as_ptr();
+        let bytes = pierce.into_inner().into_bytes();
+        assert_eq!(bytes.as_ptr(), ptr);
+        assert_eq!(bytes, "hé".as_bytes());
+    }
+}
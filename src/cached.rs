@@ -0,0 +1,109 @@
+use core::{ops::Deref, ptr::NonNull};
+
+/** The cached address of an outer pointer's target's target, kept apart from the outer pointer.
+
+Every caching type in this crate stores these next to the outer pointers they were made from:
+one in a [`Pierce`][crate::Pierce], an array of them in a [`PierceVec`][crate::PierceVec], and so on.
+Reading the target is only sound while that outer pointer is alive, which is up to the type holding both.
+
+It is `repr(transparent)`, so it has the layout of a non-null `*const` to the target.
+*/
+#[repr(transparent)]
+pub(crate) struct Cached<T>
+where
+    T: Deref,
+    T::Target: Deref,
+{
+    ptr: NonNull<<T::Target as Deref>::Target>,
+}
+
+impl<T> Cached<T>
+where
+    T: Deref,
+    T::Target: Deref,
+{
+    /** Deref `outer` twice and remember where that leads.
+     */
+    #[inline(always)]
+    #[track_caller]
+    pub(crate) fn new(outer: &T) -> Self {
+        let inner: &T::Target = outer.deref();
+        let target: &<T::Target as Deref>::Target = inner.deref();
+        Self {
+            ptr: NonNull::from(target),
+        }
+    }
+
+    /** The cached address.
+     */
+    #[inline(always)]
+    pub(crate) fn as_ptr(self) -> *mut <T::Target as Deref>::Target {
+        self.ptr.as_ptr()
+    }
+
+    /** Get the cached target.
+
+    # Safety
+    The outer pointer this was made from must be alive, and must stay alive while the returned reference is.
+    A copy of it, or a clone that [`CloneStableDeref`][crate::CloneStableDeref] promises derefs to the same place, works too.
+    Both derefs must be stable: `T` must be `StableDeref`, and `T::Target` must be `StableDeref` or deref through one, like `Pin<Q>`.
+     */
+    #[inline(always)]
+    pub(crate) unsafe fn get(&self) -> &<T::Target as Deref>::Target {
+        self.ptr.as_ref()
+        /* SAFETY:
+        The outer pointer is still alive (not dropped) when this is called.
+
+        It might have been moved, but it is StableDeref so moving is ok.
+
+        The inner pointer (which is the deref result of the outer pointer) must last as long as the outer pointer,
+        so it must still be alive too.
+
+        The target (which is the deref result of the inner pointer) must last as long as the inner pointer,
+        so it must still be alive too.
+
+        It might seem that interior mutability can cause an issue,
+        but it actually is impossible to get long-living reference out of a RefCell or Mutex,
+        so you can't deref to anything inside an interior-mutable cell anyway.
+        */
+    }
+}
+
+impl<T> Clone for Cached<T>
+where
+    T: Deref,
+    T::Target: Deref,
+{
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Cached<T>
+where
+    T: Deref,
+    T::Target: Deref,
+{
+}
+
+/* The cache is Send and Sync exactly when its outer pointer is,
+so the types holding both are Send and Sync exactly when their outer pointers are.
+The cached pointer is only ever turned into the `&Target` that deref-ing the outer pointer twice would give.
+- Sending it sends the outer pointer along, which the receiving thread could deref itself.
+  `StableDeref` guarantees that moving the outer pointer, to another thread or not, doesn't change where it derefs to.
+- Sharing it shares the outer pointer, which every thread could deref itself through `&T`.
+Whatever the target needs to be used from another thread, the outer pointer's own Send and Sync already require. */
+unsafe impl<T> Send for Cached<T>
+where
+    T: Deref + Send,
+    T::Target: Deref,
+{
+}
+
+unsafe impl<T> Sync for Cached<T>
+where
+    T: Deref + Sync,
+    T::Target: Deref,
+{
+}
@@ -0,0 +1,218 @@
+use core::{
+    cell::{Cell, UnsafeCell},
+    ops::{Deref, DerefMut},
+};
+
+use crate::{Cached, StableDeref};
+
+/** A [`Pierce`][crate::Pierce] that can be mutated, checked at runtime like a [`RefCell`][core::cell::RefCell].
+
+Shared borrows deref straight to the cached target.
+A mutable borrow gives you `&mut T`, and the target is re-cached when that borrow is dropped,
+so you can do things that move the target, like pushing to a `Vec`.
+
+```
+# use pierce::PierceCell;
+let cell = PierceCell::new(Box::new(vec![1, 2]));
+cell.borrow_mut().push(3);
+assert_eq!(&*cell.borrow(), &[1, 2, 3]);
+```
+
+Like `RefCell`, `PierceCell` is not `Sync`. It is `Send` exactly when `T` is, like [`Pierce`][crate::Pierce].
+*/
+pub struct PierceCell<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    // Positive: number of shared borrows. -1: mutably borrowed.
+    borrow: Cell<isize>,
+    target: Cell<Cached<T>>,
+    outer: UnsafeCell<T>,
+}
+
+impl<T> PierceCell<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    /** Create a new PierceCell.
+
+    This method derefs `T` twice and cache the address where the inner pointer points to,
+    just like [`Pierce::new`][crate::Pierce::new].
+     */
+    #[inline]
+    pub fn new(outer: T) -> Self {
+        Self {
+            borrow: Cell::new(0),
+            target: Cell::new(Cached::new(&outer)),
+            outer: UnsafeCell::new(outer),
+        }
+    }
+
+    /** Borrow the cached target.
+
+    # Panics
+    Panics if the PierceCell is currently mutably borrowed.
+     */
+    #[inline]
+    pub fn borrow(&self) -> PierceRef<'_, T> {
+        let count = self.borrow.get();
+        if count < 0 {
+            panic!("PierceCell already mutably borrowed");
+        }
+        self.borrow.set(count + 1);
+        PierceRef { cell: self }
+    }
+
+    /** Mutably borrow the outer pointer `T`.
+
+    The target is re-cached when the returned guard is dropped.
+
+    # Panics
+    Panics if the PierceCell is currently borrowed.
+     */
+    #[inline]
+    pub fn borrow_mut(&self) -> PierceRefMut<'_, T> {
+        if self.borrow.get() != 0 {
+            panic!("PierceCell already borrowed");
+        }
+        self.borrow.set(-1);
+        PierceRefMut { cell: self }
+    }
+
+    /** Get the outer pointer `T` out.
+     */
+    #[inline]
+    pub fn into_outer(self) -> T {
+        self.outer.into_inner()
+    }
+}
+
+/** A shared borrow of a [`PierceCell`]. Derefs to the cached target.
+*/
+pub struct PierceRef<'a, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    cell: &'a PierceCell<T>,
+}
+
+impl<T> Deref for PierceRef<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    type Target = <T::Target as Deref>::Target;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*self.cell.target.get().as_ptr() }
+        /* SAFETY:
+        The cache is only changed when a mutable borrow ends, and there is none while we exist.
+        See `Cached::get` for why the cached target is valid.
+        */
+    }
+}
+
+impl<T> Drop for PierceRef<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.cell.borrow.set(self.cell.borrow.get() - 1);
+    }
+}
+
+/** A mutable borrow of a [`PierceCell`]. Derefs to the outer pointer `T`.
+
+The target is re-cached when this is dropped.
+*/
+pub struct PierceRefMut<'a, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    cell: &'a PierceCell<T>,
+}
+
+impl<T> Deref for PierceRefMut<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &T {
+        unsafe { &*self.cell.outer.get() }
+        // SAFETY: We hold the only borrow of the cell.
+    }
+}
+
+impl<T> DerefMut for PierceRefMut<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.cell.outer.get() }
+        // SAFETY: We hold the only borrow of the cell.
+    }
+}
+
+impl<T> Drop for PierceRefMut<'_, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.cell.target.set(Cached::new(&**self));
+        self.cell.borrow.set(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_then_read() {
+        let cell = PierceCell::new(Box::new(Vec::with_capacity(1)));
+        cell.borrow_mut().push(1);
+        let before: *const [i32] = {
+            let r = cell.borrow();
+            &*r
+        };
+        // The new Vec is allocated while the old one is still alive, so the slice moves.
+        **cell.borrow_mut() = (1..100).collect();
+        let r1 = cell.borrow();
+        let r2 = cell.borrow();
+        assert_eq!(r1[98], 99);
+        let after: *const [i32] = &*r2;
+        assert_ne!(after.cast::<i32>(), before.cast::<i32>());
+        drop((r1, r2));
+        // The cache was refreshed to where the outer pointer derefs to now.
+        let outer = cell.into_outer();
+        assert_eq!(&**outer as *const [i32], after);
+    }
+
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn test_borrow_mut_while_borrowed() {
+        let cell = PierceCell::new(Box::new(String::from("hello")));
+        let _r = cell.borrow();
+        cell.borrow_mut().push('!');
+    }
+
+    #[test]
+    #[should_panic(expected = "already mutably borrowed")]
+    fn test_borrow_while_borrowed_mut() {
+        let cell = PierceCell::new(Box::new(String::from("hello")));
+        let _w = cell.borrow_mut();
+        let _ = cell.borrow().len();
+    }
+}
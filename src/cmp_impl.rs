@@ -0,0 +1,457 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+#[cfg(feature = "std")]
+use std::{
+    ffi::{CStr, OsStr},
+    path::Path,
+};
+
+use crate::{Pierce, StableDeref};
+
+/** Compare the targets by value. Each side is a single jump to its cached target.
+
+The outer pointers don't need to be the same type, or even comparable themselves.
+```
+# use std::sync::Arc;
+# use pierce::Pierce;
+let arc = Pierce::new(Arc::new(String::from("hello")));
+let boxed = Pierce::new(Box::new(String::from("hello")));
+assert_eq!(arc, boxed);
+```
+*/
+impl<T, U> PartialEq<Pierce<U>> for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    U: StableDeref,
+    U::Target: StableDeref,
+    <T::Target as Deref>::Target: PartialEq<<U::Target as Deref>::Target>,
+{
+    #[inline]
+    fn eq(&self, other: &Pierce<U>) -> bool {
+        **self == **other
+    }
+}
+
+impl<T> Eq for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Eq,
+{
+}
+
+/** Order by the targets, consistently with `PartialEq`.
+
+This lets a Pierce be sorted, or used as a key in a `BTreeMap` or a `BinaryHeap`.
+*/
+impl<T, U> PartialOrd<Pierce<U>> for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    U: StableDeref,
+    U::Target: StableDeref,
+    <T::Target as Deref>::Target: PartialOrd<<U::Target as Deref>::Target>,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Pierce<U>) -> Option<Ordering> {
+        (**self).partial_cmp(&**other)
+    }
+}
+
+impl<T> Ord for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Ord,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (**self).cmp(&**other)
+    }
+}
+
+/** Hash the target. This gives exactly the same hash as hashing the target itself.
+
+Pierces are compared by their targets, so as long as the target's `Hash` agrees with its `Eq`,
+Pierces that are equal hash the same, even if their outer pointers are different types.
+A Pierce can then be used as a `HashMap` key.
+
+To hash by address instead, see [`Pierce::hash_by_ptr`].
+*/
+impl<T> Hash for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Hash,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+/* `Borrow<Target>` can't be implemented for every target,
+because it would overlap with the standard `Borrow<T> for T` if a target were a Pierce itself.
+So, like with comparisons, the common unsized targets are listed out.
+Hash, Eq and Ord all forward to the target, so lookups with the borrowed form find the same entries. */
+macro_rules! impl_borrow {
+    ($($(#[$attr:meta])* [$($vars:tt)*] $target:ty),* $(,)?) => {$(
+        $(#[$attr])*
+        impl<$($vars)* T> Borrow<$target> for Pierce<T>
+        where
+            T: StableDeref,
+            T::Target: StableDeref<Target = $target>,
+        {
+            #[inline]
+            fn borrow(&self) -> &$target {
+                self
+            }
+        }
+    )*};
+}
+
+impl_borrow! {
+    [] str,
+    [U,] [U],
+    #[cfg(feature = "std")]
+    [] Path,
+    #[cfg(feature = "std")]
+    [] OsStr,
+    #[cfg(feature = "std")]
+    [] CStr,
+}
+
+/* Comparisons and orderings against plain values, in both directions, like `Cow<str>` has.
+Blanket `PartialEq<R>`/`PartialOrd<R>` impls for Pierce would overlap with the ones above, so the common types are listed out.
+Each entry is the type to compare with, then the types the target is actually compared and ordered with:
+references are looked through, so the target only needs to be comparable with `str`, not `&str`.
+The standard library only orders `str` against `str` and slices against slices,
+so owned types and arrays are ordered through their borrowed form. */
+macro_rules! impl_cmp {
+    ($($(#[$attr:meta])* [$($vars:tt)*] $rhs:ty => $cmp:ty, $ord:ty),* $(,)?) => {$(
+        /** Compare the target by value, not by address.
+         */
+        $(#[$attr])*
+        impl<$($vars)* T> PartialEq<$rhs> for Pierce<T>
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            <T::Target as Deref>::Target: PartialEq<$cmp>,
+        {
+            #[inline]
+            fn eq(&self, other: &$rhs) -> bool {
+                <<T::Target as Deref>::Target as PartialEq<$cmp>>::eq(self, other)
+            }
+        }
+
+        /** Compare against the target by value, not by address.
+         */
+        $(#[$attr])*
+        impl<$($vars)* T> PartialEq<Pierce<T>> for $rhs
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            $cmp: PartialEq<<T::Target as Deref>::Target>,
+        {
+            #[inline]
+            fn eq(&self, other: &Pierce<T>) -> bool {
+                <$cmp as PartialEq<<T::Target as Deref>::Target>>::eq(self, other)
+            }
+        }
+
+        /** Order the target by value.
+         */
+        $(#[$attr])*
+        impl<$($vars)* T> PartialOrd<$rhs> for Pierce<T>
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            <T::Target as Deref>::Target: PartialEq<$cmp> + PartialOrd<$ord>,
+        {
+            #[inline]
+            fn partial_cmp(&self, other: &$rhs) -> Option<Ordering> {
+                <<T::Target as Deref>::Target as PartialOrd<$ord>>::partial_cmp(self, other)
+            }
+        }
+
+        /** Order against the target by value.
+         */
+        $(#[$attr])*
+        impl<$($vars)* T> PartialOrd<Pierce<T>> for $rhs
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            $cmp: PartialEq<<T::Target as Deref>::Target>,
+            $ord: PartialOrd<<T::Target as Deref>::Target>,
+        {
+            #[inline]
+            fn partial_cmp(&self, other: &Pierce<T>) -> Option<Ordering> {
+                <$ord as PartialOrd<<T::Target as Deref>::Target>>::partial_cmp(self, other)
+            }
+        }
+    )*};
+}
+
+impl_cmp! {
+    [] str => str, str,
+    ['a,] &'a str => str, str,
+    [U,] [U] => [U], [U],
+    ['a, U,] &'a [U] => [U], [U],
+    [U, const N: usize,] [U; N] => [U; N], [U],
+    #[cfg(feature = "alloc")]
+    [] String => String, str,
+    #[cfg(feature = "alloc")]
+    [U,] Vec<U> => Vec<U>, [U],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{rc::Rc, sync::Arc};
+
+    #[test]
+    fn test_eq() {
+        let a = Pierce::new(Box::new(vec![1, 2, 3]));
+        let b = Pierce::new(Box::new(vec![1, 2, 3]));
+        let c = Pierce::new(Box::new(vec![1, 2]));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_eq_across_outer_types() {
+        let arc = Pierce::new(Arc::new(String::from("hello")));
+        let rc = Pierce::new(Rc::new(String::from("hello")));
+        let boxed = Pierce::new(Box::new(Box::new(String::from("world"))));
+        assert_eq!(arc, rc);
+        assert_eq!(rc, arc);
+        assert_ne!(Pierce::new(Box::new(String::from("world"))), rc);
+        assert_ne!(boxed, arc);
+    }
+
+    #[test]
+    fn test_eq_without_outer_eq() {
+        // An outer pointer that can't be compared itself.
+        struct NoEq(Arc<String>);
+        impl Deref for NoEq {
+            type Target = Arc<String>;
+            fn deref(&self) -> &Arc<String> {
+                &self.0
+            }
+        }
+        unsafe impl StableDeref for NoEq {}
+
+        fn assert_eq_impl<X: Eq>() {}
+        assert_eq_impl::<Pierce<NoEq>>();
+
+        let a = Pierce::new(NoEq(Arc::new(String::from("hi"))));
+        let b = Pierce::new(NoEq(Arc::new(String::from("hi"))));
+        assert!(a == b);
+        assert!(a == Pierce::new(Rc::new(String::from("hi"))));
+    }
+
+    #[test]
+    fn test_eq_str() {
+        let p = Pierce::new(Rc::new(String::from("hello")));
+        assert_eq!(p, "hello");
+        assert_eq!("hello", p);
+        assert_ne!(p, "world");
+        assert!(*"hello" == p);
+        assert!(p == *"hello");
+        assert_eq!(p, String::from("hello"));
+        assert_eq!(String::from("hello"), p);
+
+        let boxed_str = Pierce::new(Box::new(Box::<str>::from("hi")));
+        assert_eq!(boxed_str, "hi");
+        assert_eq!("hi", boxed_str);
+    }
+
+    #[test]
+    fn test_eq_slice() {
+        let p = Pierce::new(Box::new(vec![1, 2, 3]));
+        assert_eq!(p, [1, 2, 3]);
+        assert_eq!([1, 2, 3], p);
+        assert_ne!(p, [1, 2]);
+        let s: &[i32] = &[1, 2, 3];
+        assert_eq!(p, s);
+        assert_eq!(s, p);
+        assert!(p == *s);
+        assert!(*s == p);
+        assert_eq!(p, vec![1, 2, 3]);
+        assert_eq!(vec![1, 2, 3], p);
+        assert_ne!(vec![3, 2, 1], p);
+    }
+
+    #[test]
+    fn test_sort() {
+        let words = ["pear", "apple", "fig", "banana", "apple"];
+        let mut pierced: Vec<_> = words
+            .iter()
+            .map(|w| Pierce::new(Arc::new(String::from(*w))))
+            .collect();
+        pierced.sort();
+        let mut plain = words.to_vec();
+        plain.sort();
+        assert_eq!(pierced, plain);
+        assert!(pierced[0] < pierced[2]);
+        assert!(Pierce::new(Box::new(String::from("b"))) > pierced[0]);
+    }
+
+    #[test]
+    fn test_btree_map_range() {
+        use std::collections::BTreeMap;
+        let key = |s: &str| Pierce::new(Arc::new(String::from(s)));
+        let map: BTreeMap<_, _> = ["a", "b", "c", "d"]
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (key(s), i))
+            .collect();
+        let found: Vec<_> = map.range(key("b")..key("d")).map(|(_, v)| *v).collect();
+        assert_eq!(found, [1, 2]);
+        assert_eq!(map[&key("d")], 3);
+    }
+
+    #[test]
+    fn test_ord_str() {
+        let p = Pierce::new(Arc::new(Box::<str>::from("kiwi")));
+        assert!(p < "m");
+        assert!(p > "apple");
+        assert!(p <= "kiwi");
+        assert!(p >= "kiwi");
+        assert!(*"m" > p);
+        assert!("apple" < p);
+        assert_eq!(p.partial_cmp("kiwi"), Some(Ordering::Equal));
+
+        // The target here is a `str`.
+        let s = Pierce::new(Box::new(String::from("kiwi")));
+        let (m, apple) = (String::from("m"), String::from("apple"));
+        assert!(s < m);
+        assert!(apple < s);
+        assert!(s < "m");
+        assert_eq!(s.partial_cmp(&String::from("kiwi")), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn test_ord_slice() {
+        let p = Pierce::new(Box::new(Box::<[i32]>::from([1, 2, 3])));
+        let (short, bigger, two): (&[i32], &[i32], &[i32]) = (&[1, 2], &[1, 3], &[2]);
+        assert!(p >= short);
+        assert!(p < bigger);
+        assert!(two > p);
+        assert!(*short < p);
+        assert!(p <= [1, 2, 3]);
+        assert!([1, 2, 4] > p);
+
+        let v = Pierce::new(Rc::new(vec![1, 2, 3]));
+        let (short, bigger) = (vec![1, 2], vec![0, 9]);
+        assert!(v > short);
+        assert!(bigger < v);
+    }
+
+    #[test]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
+    fn test_ord_float_slice() {
+        let p = Pierce::new(Box::new(Box::<[f64]>::from([1.0, f64::NAN])));
+        let same: &[f64] = &[1.0, f64::NAN];
+        // Slices are ordered lexicographically, so a NaN only matters once the elements before it are equal.
+        assert_eq!(p.partial_cmp(same), None);
+        assert_eq!(same.partial_cmp(&p), None);
+        assert!(!(p < same));
+        assert!(!(p >= same));
+        assert!(p != same);
+        let (smaller, bigger, prefix, zero): (&[f64], &[f64], &[f64], &[f64]) =
+            (&[0.5], &[2.0, f64::NAN], &[1.0], &[1.0, 0.0]);
+        assert!(p > smaller);
+        assert!(p < bigger);
+        assert!(p > prefix);
+        assert_eq!(p.partial_cmp(zero), None);
+    }
+
+    #[test]
+    fn test_hash_map_key() {
+        use std::collections::{hash_map::RandomState, HashMap};
+        use std::hash::BuildHasher;
+
+        let key = |s: &str| Pierce::new(Arc::new(String::from(s)));
+        let mut map = HashMap::new();
+        map.insert(key("one"), 1);
+        map.insert(key("two"), 2);
+        assert_eq!(map.insert(key("one"), 3), Some(1));
+        assert_eq!(map[&key("one")], 3);
+        assert_eq!(map.get(&key("three")), None);
+
+        let state = RandomState::new();
+        let p = key("one");
+        assert_eq!(state.hash_one(&p), state.hash_one("one"));
+        assert_eq!(
+            state.hash_one(&p),
+            state.hash_one(Pierce::new(Box::new(String::from("one"))))
+        );
+    }
+
+    #[test]
+    fn test_borrow_lookup() {
+        use std::collections::{BTreeSet, HashMap, HashSet};
+        use std::path::{Path, PathBuf};
+
+        let mut map: HashMap<Pierce<Arc<String>>, i32> = HashMap::new();
+        map.insert(Pierce::new(Arc::new(String::from("key"))), 1);
+        assert_eq!(map.get("key"), Some(&1));
+        assert_eq!(map.get("other"), None);
+
+        let mut set: HashSet<Pierce<Box<Vec<u8>>>> = HashSet::new();
+        set.insert(Pierce::new(Box::new(vec![1, 2])));
+        assert!(set.contains(&[1u8, 2][..]));
+        assert!(!set.contains(&[2u8, 1][..]));
+
+        let paths: BTreeSet<Pierce<Rc<PathBuf>>> = ["/a", "/b"]
+            .iter()
+            .map(|p| Pierce::new(Rc::new(PathBuf::from(p))))
+            .collect();
+        assert!(paths.contains(Path::new("/b")));
+    }
+
+    #[test]
+    fn test_str_target() {
+        use std::collections::HashMap;
+
+        fn len_of(s: impl AsRef<str>) -> usize {
+            s.as_ref().len()
+        }
+        let boxed = Pierce::new(Box::new(String::from("hello")));
+        let arc = Pierce::new(Arc::new(String::from("hello")));
+        assert_eq!(len_of(&boxed), 5);
+        assert_eq!(len_of(arc.clone()), 5);
+        assert!(boxed == *"hello");
+        assert!(*"hello" == arc);
+
+        let mut map = HashMap::new();
+        map.insert(arc, 1);
+        assert_eq!(map.get("hello"), Some(&1));
+    }
+
+    #[test]
+    fn test_byte_slice_target() {
+        use std::collections::HashSet;
+
+        fn checksum(data: impl AsRef<[u8]>) -> u32 {
+            data.as_ref().iter().map(|b| *b as u32).sum()
+        }
+        let bytes = Pierce::new(Arc::new(vec![1u8, 2, 3]));
+        assert_eq!(checksum(&bytes), 6);
+        assert_eq!(checksum(bytes.clone()), 6);
+        assert!(bytes == *b"\x01\x02\x03".as_slice());
+        assert!(*[1u8, 2, 3].as_slice() == bytes);
+
+        let mut set = HashSet::new();
+        set.insert(bytes);
+        assert!(set.contains(b"\x01\x02\x03".as_slice()));
+    }
+}
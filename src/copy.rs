@@ -0,0 +1,142 @@
+use core::ops::Deref;
+
+use crate::{Cached, Pierce, StableDeref};
+
+/** A [`Pierce`] that is `Copy`, for outer pointers that are `Copy`.
+
+The only `Copy` outer pointers are references (and your own [`StableDeref`] types),
+so this is mostly for `PierceCopy<&'a Vec<U>>` and the like.
+Both fields are plain pointers, so copying is free and every copy derefs to the same cached target.
+
+```
+# use std::cell::Cell;
+# use pierce::PierceCopy;
+let v = vec![1, 2, 3];
+let cell = Cell::new(PierceCopy::new(&v));
+let copied = cell.get();
+assert_eq!(copied[2], 3);
+```
+*/
+pub struct PierceCopy<T>
+where
+    T: StableDeref + Copy,
+    T::Target: StableDeref,
+{
+    outer: T,
+    target: Cached<T>,
+}
+
+// PierceCopy must stay Copy for reference outer pointers.
+#[cfg(feature = "alloc")]
+const _: fn() = || {
+    fn assert_copy<C: Copy>() {}
+    assert_copy::<PierceCopy<&'static alloc::vec::Vec<u8>>>();
+};
+
+impl<T> PierceCopy<T>
+where
+    T: StableDeref + Copy,
+    T::Target: StableDeref,
+{
+    /** Create a new PierceCopy.
+
+    This method derefs `T` twice and cache the address where the inner pointer points to,
+    just like [`Pierce::new`].
+     */
+    #[inline]
+    pub fn new(outer: T) -> Self {
+        let target = Cached::new(&outer);
+        Self { outer, target }
+    }
+
+    /** Borrow the outer pointer `T`.
+     */
+    #[inline]
+    pub fn borrow_outer(&self) -> &T {
+        &self.outer
+    }
+
+    /** Get the outer pointer `T` out.
+     */
+    #[inline]
+    pub fn into_outer(self) -> T {
+        self.outer
+    }
+}
+
+impl<T> Clone for PierceCopy<T>
+where
+    T: StableDeref + Copy,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for PierceCopy<T>
+where
+    T: StableDeref + Copy,
+    T::Target: StableDeref,
+{
+}
+
+impl<T> Deref for PierceCopy<T>
+where
+    T: StableDeref + Copy,
+    T::Target: StableDeref,
+{
+    type Target = <T::Target as Deref>::Target;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.target.get() }
+        // SAFETY: `self.outer` is the outer pointer the cache was made from, or a copy of it.
+    }
+}
+
+impl<T> From<Pierce<T>> for PierceCopy<T>
+where
+    T: StableDeref + Copy,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn from(pierce: Pierce<T>) -> Self {
+        Self {
+            outer: pierce.outer,
+            target: pierce.target,
+        }
+    }
+}
+
+impl<T> From<PierceCopy<T>> for Pierce<T>
+where
+    T: StableDeref + Copy,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn from(pierce: PierceCopy<T>) -> Self {
+        Self {
+            outer: pierce.outer,
+            target: pierce.target,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copies_share_target() {
+        let v = vec![1u8, 2, 3];
+        let original = PierceCopy::new(&v);
+        let copies = [original; 4];
+        for copy in copies.iter() {
+            assert_eq!(&**copy as *const [u8], &*v as *const [u8]);
+        }
+        let pierce: Pierce<_> = original.into();
+        assert_eq!(&*pierce as *const [u8], &*original as *const [u8]);
+        assert!(core::ptr::eq(*PierceCopy::from(pierce).borrow_outer(), &v));
+    }
+}
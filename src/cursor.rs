@@ -0,0 +1,235 @@
+use std::{
+    cmp,
+    io::{self, BufRead, Cursor, Read, Seek, SeekFrom},
+    ops::Deref,
+};
+
+use crate::{Pierce, StableDeref};
+
+impl<T> Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: AsRef<[u8]>,
+{
+    /** Turn the Pierce into a reader over the bytes of its target.
+
+    This works for byte slice and `str` targets, like `Pierce<Arc<Vec<u8>>>` and `Pierce<Box<String>>`.
+    The returned cursor owns the Pierce, so it isn't tied to a borrow.
+    ```
+    # use std::io::Read;
+    # use std::sync::Arc;
+    # use pierce::Pierce;
+    let mut reader = Pierce::new(Arc::new(b"hello".to_vec())).reader();
+    let mut buf = [0; 3];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hel");
+    ```
+     */
+    #[inline]
+    pub fn reader(self) -> PierceCursor<T> {
+        PierceCursor {
+            pierce: self,
+            pos: 0,
+        }
+    }
+
+    /** Get a reader over the bytes of the target, borrowing the Pierce.
+     */
+    #[inline]
+    pub fn reader_ref(&self) -> Cursor<&[u8]> {
+        Cursor::new((**self).as_ref())
+    }
+}
+
+/** A reader over the bytes of a [`Pierce`]'s target. Created with [`Pierce::reader`].
+
+This works like [`std::io::Cursor`]: it keeps a position, and implements [`Read`], [`BufRead`] and [`Seek`].
+Every read slices the cached target directly.
+*/
+pub struct PierceCursor<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: AsRef<[u8]>,
+{
+    pierce: Pierce<T>,
+    pos: u64,
+}
+
+impl<T> PierceCursor<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: AsRef<[u8]>,
+{
+    /** The current position, in bytes from the start.
+     */
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /** Set the position, in bytes from the start. It may be past the end.
+     */
+    #[inline]
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+
+    /** Borrow the Pierce being read.
+     */
+    #[inline]
+    pub fn get_ref(&self) -> &Pierce<T> {
+        &self.pierce
+    }
+
+    /** Get the Pierce out.
+     */
+    #[inline]
+    pub fn into_inner(self) -> Pierce<T> {
+        self.pierce
+    }
+
+    #[inline]
+    fn remaining(&self) -> &[u8] {
+        let bytes = (*self.pierce).as_ref();
+        let start = cmp::min(self.pos, bytes.len() as u64) as usize;
+        &bytes[start..]
+    }
+}
+
+impl<T> Read for PierceCursor<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: AsRef<[u8]>,
+{
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.remaining().read(buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<T> BufRead for PierceCursor<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: AsRef<[u8]>,
+{
+    #[inline]
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(self.remaining())
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt as u64;
+    }
+}
+
+impl<T> Seek for PierceCursor<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: AsRef<[u8]>,
+{
+    fn seek(&mut self, style: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match style {
+            SeekFrom::Start(n) => {
+                self.pos = n;
+                return Ok(n);
+            }
+            SeekFrom::End(n) => ((*self.pierce).as_ref().len() as u64, n),
+            SeekFrom::Current(n) => (self.pos, n),
+        };
+        match base.checked_add_signed(offset) {
+            Some(n) => {
+                self.pos = n;
+                Ok(n)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    const DATA: &[u8] = b"the quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn test_read_chunks() {
+        let mut reader = Pierce::new(Arc::new(DATA.to_vec())).reader();
+        let mut out = Vec::new();
+        let mut buf = [0; 7];
+        loop {
+            let n = reader.read(&mut buf).unwrap();
+            if n == 0 {
+                break;
+            }
+            out.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(out, DATA);
+        assert_eq!(reader.position(), DATA.len() as u64);
+        assert_eq!(reader.into_inner(), DATA);
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut reader = Pierce::new(Box::new(String::from("0123456789"))).reader();
+        let mut byte = [0];
+        assert_eq!(reader.seek(SeekFrom::Start(3)).unwrap(), 3);
+        reader.read_exact(&mut byte).unwrap();
+        assert_eq!(&byte, b"3");
+        assert_eq!(reader.seek(SeekFrom::Current(2)).unwrap(), 6);
+        assert_eq!(reader.seek(SeekFrom::Current(-1)).unwrap(), 5);
+        reader.read_exact(&mut byte).unwrap();
+        assert_eq!(&byte, b"5");
+        assert_eq!(reader.seek(SeekFrom::End(-2)).unwrap(), 8);
+        reader.read_exact(&mut byte).unwrap();
+        assert_eq!(&byte, b"8");
+        assert!(reader.seek(SeekFrom::Current(-100)).is_err());
+        assert_eq!(reader.position(), 9);
+        // Past the end is allowed, and reads nothing.
+        assert_eq!(reader.seek(SeekFrom::End(5)).unwrap(), 15);
+        assert_eq!(reader.read(&mut byte).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_buf_read_matches_std_cursor() {
+        let mut ours = Pierce::new(Arc::new(DATA.to_vec())).reader();
+        let mut std_cursor = Cursor::new(DATA);
+        for amt in [0, 3, 1, 10, 100] {
+            assert_eq!(ours.fill_buf().unwrap(), std_cursor.fill_buf().unwrap());
+            ours.consume(amt);
+            std_cursor.consume(amt);
+            assert_eq!(ours.position(), std_cursor.position());
+        }
+        assert_eq!(ours.fill_buf().unwrap(), b"");
+
+        let mut ours = Pierce::new(Box::new(String::from("a\nbb\nccc"))).reader();
+        let lines: Vec<String> = (&mut ours).lines().map(Result::unwrap).collect();
+        assert_eq!(lines, ["a", "bb", "ccc"]);
+    }
+
+    #[test]
+    fn test_reader_ref() {
+        let pierce = Pierce::new(Arc::new(DATA.to_vec()));
+        let mut word = String::new();
+        pierce
+            .reader_ref()
+            .take(3)
+            .read_to_string(&mut word)
+            .unwrap();
+        assert_eq!(word, "the");
+        assert_eq!(pierce.reader_ref().get_ref().as_ptr(), pierce.as_ptr());
+    }
+}
@@ -0,0 +1,160 @@
+use alloc::boxed::Box;
+use core::{ops::Deref, ptr::NonNull};
+
+use crate::{Pierce, StableDeref};
+
+/* The type-erased owner of the target. Implemented by the two wrappers below,
+which only differ in whether they can be cloned. */
+trait ErasedOuter<U: ?Sized>: Send + Sync {
+    fn try_clone(&self) -> Option<ErasedPierce<U>>;
+}
+
+struct Unclonable<T>(T);
+
+impl<T, U> ErasedOuter<U> for Unclonable<Pierce<T>>
+where
+    T: StableDeref,
+    T::Target: StableDeref<Target = U>,
+    U: ?Sized,
+    Pierce<T>: Send + Sync,
+{
+    fn try_clone(&self) -> Option<ErasedPierce<U>> {
+        None
+    }
+}
+
+struct Clonable<T>(T);
+
+impl<T, U> ErasedOuter<U> for Clonable<Pierce<T>>
+where
+    T: StableDeref + Clone + 'static,
+    T::Target: StableDeref<Target = U>,
+    U: ?Sized,
+    Pierce<T>: Send + Sync,
+{
+    fn try_clone(&self) -> Option<ErasedPierce<U>> {
+        Some(ErasedPierce::new_clonable(self.0.clone()))
+    }
+}
+
+/** A cached target of type `U`, with the type of the outer pointer erased.
+
+Use this to hand out "a cached `&str`" without exposing whether it is owned by an `Arc<String>` or a `Box<String>`.
+The outer pointer is moved into a box, which doesn't move the target.
+Deref-ing still goes straight to the cached target.
+
+```
+# use std::sync::Arc;
+# use pierce::{ErasedPierce, Pierce};
+let names: Vec<ErasedPierce<str>> = vec![
+    Pierce::new(Arc::new(String::from("arc"))).into(),
+    Pierce::new(Box::new(String::from("box"))).into(),
+];
+assert_eq!(&*names[0], "arc");
+assert_eq!(&*names[1], "box");
+```
+
+The outer pointer must be `Send + Sync + 'static`.
+*/
+pub struct ErasedPierce<U: ?Sized> {
+    target: NonNull<U>,
+    outer: Box<dyn ErasedOuter<U>>,
+}
+
+impl<U: ?Sized> ErasedPierce<U> {
+    /** Erase the outer pointer type of a Pierce.
+
+    The result can't be cloned. Use [`new_clonable`][Self::new_clonable] if you need [`try_clone`][Self::try_clone].
+     */
+    #[inline]
+    pub fn new<T>(pierce: Pierce<T>) -> Self
+    where
+        T: StableDeref + 'static,
+        T::Target: StableDeref<Target = U>,
+        Pierce<T>: Send + Sync,
+    {
+        let target = NonNull::from(&*pierce);
+        Self {
+            target,
+            outer: Box::new(Unclonable(pierce)),
+        }
+    }
+
+    /** Erase the outer pointer type of a Pierce, remembering how to clone it.
+     */
+    #[inline]
+    pub fn new_clonable<T>(pierce: Pierce<T>) -> Self
+    where
+        T: StableDeref + Clone + 'static,
+        T::Target: StableDeref<Target = U>,
+        Pierce<T>: Send + Sync,
+    {
+        let target = NonNull::from(&*pierce);
+        Self {
+            target,
+            outer: Box::new(Clonable(pierce)),
+        }
+    }
+
+    /** Clone the outer pointer, if this was created with [`new_clonable`][Self::new_clonable].
+     */
+    #[inline]
+    pub fn try_clone(&self) -> Option<Self> {
+        self.outer.try_clone()
+    }
+}
+
+/* The outer pointer was a Send + Sync Pierce pointing to U, so sending or sharing us is just like sending or sharing it. */
+unsafe impl<U: ?Sized + Sync> Send for ErasedPierce<U> {}
+
+unsafe impl<U: ?Sized + Sync> Sync for ErasedPierce<U> {}
+
+impl<U: ?Sized> Deref for ErasedPierce<U> {
+    type Target = U;
+    #[inline]
+    fn deref(&self) -> &U {
+        unsafe { self.target.as_ref() }
+        // SAFETY: The target is the cached target of the Pierce kept alive in `self.outer`. Moving a Pierce into the box is ok.
+    }
+}
+
+impl<T, U> From<Pierce<T>> for ErasedPierce<U>
+where
+    T: StableDeref + 'static,
+    T::Target: StableDeref<Target = U>,
+    U: ?Sized,
+    Pierce<T>: Send + Sync,
+{
+    #[inline]
+    fn from(pierce: Pierce<T>) -> Self {
+        Self::new(pierce)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_erase_different_outers() {
+        let arc = Arc::new(String::from("shared"));
+        let boxed = Box::new(String::from("owned"));
+        let boxed_target: *const str = &**boxed;
+        let mut erased: Vec<ErasedPierce<str>> = vec![
+            ErasedPierce::new_clonable(Pierce::new(arc.clone())),
+            ErasedPierce::new(Pierce::new(boxed)),
+        ];
+        assert_eq!(&*erased[0] as *const str, &**arc as *const str);
+        assert_eq!(&*erased[1] as *const str, boxed_target);
+
+        let cloned = erased[0].try_clone().unwrap();
+        assert!(erased[1].try_clone().is_none());
+        assert_eq!(Arc::strong_count(&arc), 3);
+        erased.push(cloned);
+
+        let handle = std::thread::spawn(move || erased.iter().map(|s| s.len()).sum::<usize>());
+        assert_eq!(handle.join().unwrap(), 17);
+        assert_eq!(Arc::strong_count(&arc), 1);
+    }
+}
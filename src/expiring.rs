@@ -0,0 +1,214 @@
+use core::{
+    cell::Cell,
+    ops::Deref,
+    ptr::NonNull,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/** A source of generation numbers for [`ExpiringPierce`].
+
+Implemented for `&AtomicU64` (read with `Acquire`) and for closures returning `u64`.
+*/
+pub trait Generation {
+    /** The current generation.
+     */
+    fn current(&self) -> u64;
+}
+
+impl Generation for &AtomicU64 {
+    #[inline]
+    fn current(&self) -> u64 {
+        self.load(Ordering::Acquire)
+    }
+}
+
+impl<F: Fn() -> u64> Generation for F {
+    #[inline]
+    fn current(&self) -> u64 {
+        self()
+    }
+}
+
+/** A cache of a nested pointer whose target may change, invalidated by a generation number.
+
+A [`Pierce`][crate::Pierce] requires the target to never move, and would serve stale data otherwise.
+`ExpiringPierce` is for outer pointers whose double-deref target can legitimately change,
+as long as the change is announced by bumping an external generation number.
+
+On each deref, the current generation is compared to the one the cache was made with.
+If they're the same, the cached target is returned. Otherwise, `T` is deref-ed twice and the result is re-cached.
+So the fast path is one generation read, one compare, and one jump.
+
+```
+# use std::sync::atomic::{AtomicU64, Ordering};
+# use pierce::ExpiringPierce;
+let version = AtomicU64::new(0);
+let data = Box::new(vec![1, 2, 3]);
+// SAFETY: `data` isn't changed at all.
+let pierce = unsafe { ExpiringPierce::new(&data, &version) };
+assert_eq!(pierce.len(), 3);
+version.fetch_add(1, Ordering::Release);
+assert_eq!(pierce.len(), 3); // Re-cached.
+```
+
+An `ExpiringPierce` can't be shared between threads, since the cache is updated through `&self`.
+*/
+pub struct ExpiringPierce<T, G>
+where
+    T: Deref,
+    T::Target: Deref,
+    G: Generation,
+{
+    outer: T,
+    generation: G,
+    cached_generation: Cell<u64>,
+    target: Cell<Option<NonNull<<T::Target as Deref>::Target>>>,
+}
+
+impl<T, G> ExpiringPierce<T, G>
+where
+    T: Deref,
+    T::Target: Deref,
+    G: Generation,
+{
+    /** Create a new ExpiringPierce. The target is cached on the first deref.
+
+    # Safety
+    While `generation` keeps returning the same value, deref-ing `outer` twice must keep returning the same reference,
+    and that reference must stay valid.
+    In other words, anything that moves or frees the target must also change the generation before the next deref.
+     */
+    #[inline]
+    pub unsafe fn new(outer: T, generation: G) -> Self {
+        Self {
+            outer,
+            generation,
+            cached_generation: Cell::new(0),
+            target: Cell::new(None),
+        }
+    }
+
+    /** Throw away the cached target. The next deref will re-cache.
+     */
+    #[inline]
+    pub fn invalidate(&self) {
+        self.target.set(None);
+    }
+
+    /** Borrow the outer pointer `T`.
+     */
+    #[inline]
+    pub fn borrow_outer(&self) -> &T {
+        &self.outer
+    }
+
+    /** Mutably borrow the outer pointer `T`. The cache is invalidated.
+     */
+    #[inline]
+    pub fn borrow_outer_mut(&mut self) -> &mut T {
+        self.target.set(None);
+        &mut self.outer
+    }
+
+    /** Get the outer pointer `T` out.
+     */
+    #[inline]
+    pub fn into_outer(self) -> T {
+        self.outer
+    }
+}
+
+impl<T, G> Deref for ExpiringPierce<T, G>
+where
+    T: Deref,
+    T::Target: Deref,
+    G: Generation,
+{
+    type Target = <T::Target as Deref>::Target;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        let generation = self.generation.current();
+        if let Some(target) = self.target.get() {
+            if self.cached_generation.get() == generation {
+                return unsafe { target.as_ref() };
+                /* SAFETY:
+                The target was cached in this generation from `self.outer`, which we still own.
+                The caller of `new` promised that deref-ing it again would return this same reference.
+                */
+            }
+        }
+        let target = self.outer.deref().deref();
+        self.cached_generation.set(generation);
+        self.target.set(Some(NonNull::from(target)));
+        target
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    /* A pointer whose target can be swapped out through `&self`, like a hand-rolled copy-on-write structure.
+    Replaced vecs are kept alive in `retired`, so a stale cache would read old data instead of crashing. */
+    struct Swappable {
+        current: RefCell<Vec<u32>>,
+        retired: RefCell<Vec<Vec<u32>>>,
+        version: Cell<u64>,
+    }
+
+    impl Swappable {
+        fn replace(&self, new: Vec<u32>) {
+            let old = self.current.replace(new);
+            self.retired.borrow_mut().push(old);
+            self.version.set(self.version.get() + 1);
+        }
+    }
+
+    impl Deref for Swappable {
+        type Target = [u32];
+        fn deref(&self) -> &[u32] {
+            unsafe { &*self.current.as_ptr() }
+        }
+    }
+
+    #[test]
+    fn test_never_stale() {
+        let swappable = Swappable {
+            current: RefCell::new(vec![0]),
+            retired: RefCell::new(Vec::new()),
+            version: Cell::new(0),
+        };
+        let pierce = unsafe { ExpiringPierce::new(&swappable, || swappable.version.get()) };
+        for i in 1..100 {
+            assert_eq!(pierce[0], i - 1);
+            assert_eq!(pierce[0], i - 1);
+            swappable.replace(vec![i]);
+        }
+        assert_eq!(pierce.as_ptr(), swappable.current.borrow().as_ptr());
+    }
+
+    #[test]
+    fn test_atomic_generation_and_invalidate() {
+        let derefs = Cell::new(0);
+        struct Counting<'a>(&'a Cell<u32>, String);
+        impl Deref for Counting<'_> {
+            type Target = String;
+            fn deref(&self) -> &String {
+                self.0.set(self.0.get() + 1);
+                &self.1
+            }
+        }
+        let version = AtomicU64::new(0);
+        let pierce = unsafe { ExpiringPierce::new(Counting(&derefs, "hi".into()), &version) };
+        assert_eq!(&*pierce, "hi");
+        assert_eq!(&*pierce, "hi");
+        assert_eq!(derefs.get(), 1);
+        version.store(1, Ordering::Release);
+        assert_eq!(&*pierce, "hi");
+        assert_eq!(derefs.get(), 2);
+        pierce.invalidate();
+        assert_eq!(&*pierce, "hi");
+        assert_eq!(derefs.get(), 3);
+    }
+}
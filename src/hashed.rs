@@ -0,0 +1,273 @@
+use core::{
+    fmt,
+    hash::{BuildHasher, Hash, Hasher},
+    ops::Deref,
+};
+
+use crate::{Pierce, StableDeref};
+
+/** A [`Pierce`] that remembers the hash of its target.
+
+The target is hashed once, with a [`BuildHasher`] given at construction.
+After that, hashing a `HashedPierce` just feeds the remembered `u64` to the hasher,
+so looking up long strings in several maps doesn't walk the bytes every time.
+Equality still compares the full targets.
+
+All the `HashedPierce`s that go in the same map must be made with the same `BuildHasher`
+(or clones of it), otherwise equal targets can hash differently.
+The map itself can use any hasher.
+Looking up by `&str` or `&[U]` doesn't work, since those hash differently; look up with another `HashedPierce`.
+
+```
+# use std::collections::HashMap;
+# use std::collections::hash_map::RandomState;
+# use std::sync::Arc;
+# use pierce::HashedPierce;
+let hasher = RandomState::new();
+let key = HashedPierce::new(Arc::new(String::from("a long key")), &hasher);
+let mut map = HashMap::new();
+map.insert(key.clone(), 1);
+assert_eq!(map.get(&key), Some(&1));
+let probe = HashedPierce::new(Arc::new(String::from("a long key")), &hasher);
+assert_eq!(map.get(&probe), Some(&1));
+```
+*/
+pub struct HashedPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    pierce: Pierce<T>,
+    hash: u64,
+}
+
+impl<T> HashedPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Hash,
+{
+    /** Create a new HashedPierce, hashing the target with `build_hasher`.
+     */
+    #[inline]
+    pub fn new<S: BuildHasher>(outer: T, build_hasher: &S) -> Self {
+        Self::from_pierce(Pierce::new(outer), build_hasher)
+    }
+
+    /** Hash the target of an existing Pierce with `build_hasher`.
+     */
+    #[inline]
+    pub fn from_pierce<S: BuildHasher>(pierce: Pierce<T>, build_hasher: &S) -> Self {
+        let hash = build_hasher.hash_one(&*pierce);
+        Self { pierce, hash }
+    }
+
+    /** Hash the target again with `build_hasher`.
+
+    A Pierce's target can't be changed through the Pierce, so this is only needed
+    to switch to another `BuildHasher`, or if the target's hash can change through interior mutability.
+     */
+    #[inline]
+    pub fn refresh<S: BuildHasher>(&mut self, build_hasher: &S) {
+        self.hash = build_hasher.hash_one(&*self.pierce);
+    }
+}
+
+impl<T> HashedPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    /** The remembered hash of the target.
+     */
+    #[inline]
+    pub fn memoized_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /** Borrow the Pierce.
+     */
+    #[inline]
+    pub fn as_pierce(&self) -> &Pierce<T> {
+        &self.pierce
+    }
+
+    /** Get the Pierce out, forgetting the hash.
+     */
+    #[inline]
+    pub fn into_pierce(self) -> Pierce<T> {
+        self.pierce
+    }
+}
+
+impl<T> Deref for HashedPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    type Target = <T::Target as Deref>::Target;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.pierce
+    }
+}
+
+impl<T> Hash for HashedPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+impl<T> PartialEq for HashedPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T> Eq for HashedPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Eq,
+{
+}
+
+impl<T> Clone for HashedPierce<T>
+where
+    T: StableDeref + Clone,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            pierce: self.pierce.clone(),
+            hash: self.hash,
+        }
+    }
+}
+
+impl<T> fmt::Debug for HashedPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashedPierce")
+            .field("target", &&**self)
+            .field("hash", &self.hash)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::collections::hash_map::{DefaultHasher, RandomState};
+    use std::collections::{HashMap, HashSet};
+    use std::hash::BuildHasherDefault;
+    use std::sync::Arc;
+
+    // Counts how many bytes were fed to it.
+    #[derive(Default)]
+    struct CountingHasher {
+        inner: DefaultHasher,
+        bytes: usize,
+    }
+    thread_local! {
+        static BYTES: Cell<usize> = const { Cell::new(0) };
+    }
+    impl Hasher for CountingHasher {
+        fn finish(&self) -> u64 {
+            BYTES.with(|b| b.set(b.get() + self.bytes));
+            self.inner.finish()
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            self.bytes += bytes.len();
+            self.inner.write(bytes);
+        }
+    }
+
+    #[test]
+    fn test_lookups_match_plain_pierce() {
+        let words = [
+            "apple",
+            "banana",
+            "cherry",
+            "a much longer key than the others",
+            "",
+        ];
+        let hasher = RandomState::new();
+        let mut plain = HashMap::new();
+        let mut hashed = HashMap::new();
+        for (i, word) in words.iter().enumerate() {
+            plain.insert(Pierce::new(Arc::new(word.to_string())), i);
+            hashed.insert(HashedPierce::new(Arc::new(word.to_string()), &hasher), i);
+        }
+        for probe in words.iter().chain(&["durian", "appl"]) {
+            let by_plain = plain.get(&Pierce::new(Arc::new(probe.to_string())));
+            let by_hashed = hashed.get(&HashedPierce::new(Arc::new(probe.to_string()), &hasher));
+            assert_eq!(by_plain, by_hashed, "{probe}");
+        }
+    }
+
+    #[test]
+    fn test_hashes_target_once() {
+        let build = BuildHasherDefault::<CountingHasher>::default();
+        let long = "x".repeat(10_000);
+        let key = HashedPierce::new(Box::new(long.clone()), &build);
+        assert!(BYTES.with(Cell::get) >= 10_000);
+        assert_eq!(key.memoized_hash(), build.hash_one(long.as_str()));
+
+        let mut set = HashSet::with_hasher(build.clone());
+        BYTES.with(|b| b.set(0));
+        set.insert(key.clone());
+        for _ in 0..100 {
+            assert!(set.contains(&key));
+        }
+        // Only the remembered u64s were hashed.
+        assert_eq!(BYTES.with(Cell::get), 101 * 8);
+    }
+
+    #[test]
+    fn test_eq_compares_targets() {
+        let hasher = RandomState::new();
+        let a = HashedPierce::new(Box::new(vec![1, 2]), &hasher);
+        let b = HashedPierce::new(Box::new(vec![1, 2]), &hasher);
+        let c = HashedPierce::new(Box::new(vec![1, 3]), &hasher);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.memoized_hash(), b.memoized_hash());
+        assert_eq!(
+            format!("{:?}", a),
+            format!(
+                "HashedPierce {{ target: [1, 2], hash: {} }}",
+                a.memoized_hash()
+            )
+        );
+    }
+
+    #[test]
+    fn test_refresh() {
+        let first = RandomState::new();
+        let second = RandomState::new();
+        let mut key = HashedPierce::from_pierce(Pierce::new(Arc::new(String::from("k"))), &first);
+        assert_eq!(key.memoized_hash(), first.hash_one("k"));
+        key.refresh(&second);
+        assert_eq!(key.memoized_hash(), second.hash_one("k"));
+        assert_eq!(key.as_pierce(), "k");
+        assert_eq!(key.into_pierce(), "k");
+    }
+}
@@ -121,16 +121,16 @@ That said, here are my results:
 
 Time taken by `Pierce<T>` version compared to `T` version.
 
-| Run		| Benchmark 1		| Benchmark 2	 	| Benchmark 3       |
+| Run       | Benchmark 1       | Benchmark 2       | Benchmark 3       |
 |-----------|-------------------|-------------------|-------------------|
-| 1			| -40.23%			| -99.69%			| -5.68%            |
-| 2			| -40.59%			| -99.69%			| -5.16%            |
-| 3			| -40.70%			| -99.68%			| +2.69%            |
-| 4			| -39.85%			| -99.68%			| -5.35%            |
-| 5			| -38.90%			| -99.71%			| -5.02%            |
-| 6			| -39.12%			| -99.69%			| -5.53%            |
-| 7			| -40.51%			| -99.69%			| -6.09%            |
-| 8			| -26.99%			| -99.71%			| -6.43%            |
+| 1         | -40.23%           | -99.69%           | -5.68%            |
+| 2         | -40.59%           | -99.69%           | -5.16%            |
+| 3         | -40.70%           | -99.68%           | +2.69%            |
+| 4         | -39.85%           | -99.68%           | -5.35%            |
+| 5         | -38.90%           | -99.71%           | -5.02%            |
+| 6         | -39.12%           | -99.69%           | -5.53%            |
+| 7         | -40.51%           | -99.69%           | -6.09%            |
+| 8         | -26.99%           | -99.71%           | -6.43%            |
 
 See the benchmarks' code [here](https://github.com/wishawa/pierce/tree/main/src/bin/benchmark/main.rs).
 
@@ -150,15 +150,190 @@ The trait is re-exported at `pierce::StableDeref`.
 
 The vast majority of pointers are `StableDeref`,
 including [Box], [Vec], [String], [Rc][std::rc::Rc], [Arc][std::sync::Arc].
+
+## Debug Builds
+
+With debug assertions on, every deref of a Pierce also derefs the outer pointer twice to check the cache,
+so debug builds don't get the speedup, and an outer pointer whose `Deref` has side effects runs them on every access.
+
+# Optional Features
+
+* `std` (default): enables `alloc`, `AtomicPierce`, `PierceMap`, `PierceCursor` and the `lock` module. Without it, the crate is `#![no_std]`.
+  `Pierce` itself only needs `core`.
+* `alloc`: the helpers that need a heap, like `PierceVec`, `WeakPierce`, `ErasedPierce`, `SharedStr` and `PierceString`.
+* `serde`: `Pierce<T>` implements `Serialize`, serializing as its target,
+  and `Deserialize` for outer pointers that implement `PierceConstruct` (`Box`, `Rc`, `Arc`).
+  With `std` too, `#[serde(with = "pierce::serde_shared")]` writes Pierces sharing an `Rc` or `Arc` once, and reads them back shared.
+* `arbitrary`: `Pierce<T>`, `OptionPierce<T>` and `PierceVec<T>` implement `arbitrary::Arbitrary` when `T` does, for fuzzing with `cargo fuzz`.
+* `bincode`: `Pierce<T>` implements bincode 2's `Encode` as its target, and `Decode` and `BorrowDecode` by decoding `T`.
+  The bytes are the same as for the unwrapped value.
+* `proptest`: `Pierce<T>` implements proptest's `Arbitrary` when `T` does, so `any::<Pierce<T>>()` works.
+* `quickcheck`: `Pierce<T>` implements quickcheck's `Arbitrary` when `T` does, generating and shrinking through `T`.
+* `rayon`: `pierce.par_iter()` works when the target can be iterated in parallel by reference, like a `Vec` or a slice.
+* `rkyv`: `Pierce<T>` implements rkyv's `Archive`, `Serialize` and `Deserialize` by delegating to `T`.
+  The archived form is `ArchivedPierce<T>`, and the cache is rebuilt on deserialization.
+  No rkyv `size_*` feature is enabled, so the one picked by your own rkyv dependency is used.
+* `rkyv-validation`: enables rkyv's `validation` and implements `CheckBytes` for `ArchivedPierce<T>`,
+  so it can be validated with `check_archived_root`.
+* `nightly`: `Pierce<T>` implements `Fn`, `FnMut` and `FnOnce` when the target is `Fn`, so a pierced callback can be called as `handler(event)`.
+  Methods with a `self: Pierce<..>` receiver work with `arbitrary_self_types`, since Pierce is `Deref`.
+  Requires a nightly compiler.
 */
 
-use std::{ops::Deref, ptr::NonNull};
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![cfg_attr(feature = "nightly", feature(fn_traits, tuple_trait, unboxed_closures))]
+#![cfg_attr(all(test, feature = "nightly"), feature(arbitrary_self_types))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    panic::{RefUnwindSafe, UnwindSafe},
+    str::FromStr,
+};
+
+pub use stable_deref_trait::{CloneStableDeref, StableDeref};
+
+#[cfg(feature = "std")]
+mod atomic;
+#[cfg(feature = "std")]
+pub use atomic::{AtomicPierce, AtomicPierceGuard};
+
+mod borrowed;
+pub use borrowed::PierceBorrowed;
+
+#[cfg(feature = "alloc")]
+mod boxed;
+
+mod cached;
+use cached::Cached;
+
+mod cell;
+pub use cell::{PierceCell, PierceRef, PierceRefMut};
+
+mod cmp_impl;
+
+#[cfg(feature = "std")]
+pub mod lock;
+
+mod copy;
+pub use copy::PierceCopy;
+
+#[cfg(feature = "std")]
+mod cursor;
+#[cfg(feature = "std")]
+pub use cursor::PierceCursor;
+
+#[cfg(feature = "alloc")]
+mod erased;
+#[cfg(feature = "alloc")]
+pub use erased::ErasedPierce;
+
+mod expiring;
+pub use expiring::{ExpiringPierce, Generation};
+
+mod hashed;
+pub use hashed::HashedPierce;
+
+mod multi_pierce;
+pub use multi_pierce::{PiercePair, PierceQuad, PierceTriple};
+
+mod ops_impl;
+
+mod option_pierce;
+pub use option_pierce::OptionPierce;
+
+mod pinned;
+pub use pinned::PinnedPierce;
+
+mod pierced;
+pub use pierced::Pierced;
+
+mod pierce_array;
+pub use pierce_array::PierceArray;
+
+mod pierce_c;
+pub use pierce_c::PierceC;
+
+#[cfg(feature = "std")]
+mod pierce_map;
+#[cfg(feature = "std")]
+pub use pierce_map::{PierceMap, PierceMapIter};
 
-pub use stable_deref_trait::StableDeref;
+#[cfg(feature = "alloc")]
+mod pierce_string;
+#[cfg(feature = "alloc")]
+pub use pierce_string::PierceString;
+
+#[cfg(feature = "alloc")]
+mod pierce_vec;
+#[cfg(feature = "alloc")]
+pub use pierce_vec::PierceVec;
+
+#[cfg(feature = "alloc")]
+mod refcounted;
+
+mod slice;
+
+#[cfg(feature = "alloc")]
+mod shared_str;
+#[cfg(feature = "alloc")]
+pub use shared_str::SharedStr;
+
+#[cfg(feature = "alloc")]
+mod vec_ext;
+#[cfg(feature = "alloc")]
+pub use vec_ext::PierceVecExt;
+
+#[cfg(feature = "alloc")]
+mod weak;
+#[cfg(feature = "alloc")]
+pub use weak::{Downgrade, WeakPierce};
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+
+#[cfg(feature = "bincode")]
+mod bincode_impl;
+
+#[cfg(feature = "nightly")]
+mod nightly;
+
+#[cfg(feature = "proptest")]
+mod proptest_impl;
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck_impl;
+
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+
+#[cfg(feature = "rkyv")]
+mod rkyv_impl;
+#[cfg(feature = "rkyv")]
+pub use rkyv_impl::ArchivedPierce;
+
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(feature = "serde")]
+pub use serde_impl::PierceConstruct;
+#[cfg(all(feature = "serde", feature = "std"))]
+pub mod serde_shared;
 
 /** Cache doubly-nested pointers.
 
 A `Pierce<T>` stores `T` along with a cached pointer to `<T::Target as Deref>::Target`.
+
+# Dropping
+
+Pierce has no `Drop` impl of its own, and the cached pointer is a plain pointer with nothing to drop.
+Dropping a Pierce just drops the outer pointer, which drops the inner pointer and the target the way it always would.
+The cache is never read during or after that, since nothing can reach the Pierce once it starts dropping.
+When the outer pointer is shared, like an `Rc`, only the last owner to be dropped drops the inner pointer.
+[`into_outer`][Pierce::into_outer] drops nothing; it discards the cache and gives back the outer pointer.
 */
 pub struct Pierce<T>
 where
@@ -166,7 +341,48 @@ where
     T::Target: StableDeref,
 {
     outer: T,
-    target: NonNull<<T::Target as Deref>::Target>,
+    target: Cached<T>,
+}
+
+/* A Pierce must be no bigger than its outer pointer plus one pointer to the target.
+The target of `Arc<Vec<u8>>` is `[u8]`, so that pointer is a fat `*const [u8]`. */
+#[cfg(feature = "alloc")]
+const _: () = assert!(
+    core::mem::size_of::<Pierce<alloc::sync::Arc<alloc::vec::Vec<u8>>>>()
+        == core::mem::size_of::<alloc::sync::Arc<alloc::vec::Vec<u8>>>()
+            + core::mem::size_of::<*const [u8]>(),
+    "Pierce size invariant violated"
+);
+const _: () = assert!(
+    core::mem::size_of::<Pierce<&&u64>>()
+        == core::mem::size_of::<&&u64>() + core::mem::size_of::<*const u64>(),
+    "Pierce size invariant violated"
+);
+
+/** Pointers to pointers, which is what [`Pierce::new`] takes.
+
+This is implemented for every `T: StableDeref` whose target is `StableDeref` too, and can't be implemented otherwise.
+It only exists so that wrapping a single pointer, like `Pierce::new(Arc::new(5))`,
+fails with an error that says what's wrong, instead of a missing `StableDeref` impl for the integer.
+*/
+#[diagnostic::on_unimplemented(
+    message = "`Pierce` needs a pointer to a pointer, but the target of `{Self}` is not a `StableDeref` pointer",
+    label = "this only derefs once",
+    note = "a Pierce caches the target of the target, like the `[T]` of an `Arc<Vec<T>>` or the `T` of a `Box<Box<T>>`",
+    note = "a single pointer like `Arc<T>` or `Vec<T>` already reaches its target in one jump, so it doesn't need a Pierce"
+)]
+pub trait DoublyDeref: StableDeref + sealed::Sealed {}
+#[diagnostic::do_not_recommend]
+impl<T> DoublyDeref for T
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl<T: ?Sized> Sealed for T {}
 }
 
 impl<T> Pierce<T>
@@ -179,13 +395,19 @@ where
     Create a Pierce out of the given nested pointer.
     This method derefs `T` twice and cache the address where the inner pointer points to.
 
-    Deref-ing the created Pierce returns the cached reference directly. `deref` is not called on `T`.
+    Deref-ing the created Pierce returns the cached reference directly.
+    `deref` is not called on `T` in release builds; debug builds also re-deref `T` to check the cache.
+
+    This is `#[track_caller]`, so a panic from a `#[track_caller]` deref of the outer or inner pointer
+    is reported at the call to `Pierce::new`, not inside Pierce.
      */
-    #[inline]
-    pub fn new(outer: T) -> Self {
-        let inner: &T::Target = outer.deref();
-        let target: &<T::Target as Deref>::Target = inner.deref();
-        let target = NonNull::from(target);
+    #[inline(always)]
+    #[track_caller]
+    pub fn new(outer: T) -> Self
+    where
+        T: DoublyDeref,
+    {
+        let target = Cached::new(&outer);
         Self { outer, target }
     }
 
@@ -205,7 +427,7 @@ where
     ```
 
     */
-    #[inline]
+    #[inline(always)]
     pub fn borrow_outer(&self) -> &T {
         &self.outer
     }
@@ -218,27 +440,157 @@ where
     pub fn into_outer(self) -> T {
         self.outer
     }
+
+    /** Hash the address of the cached target, not the target's value.
+
+    Two Pierces that share a target, like ones made from clones of the same `Arc`, hash the same.
+    Pierces with equal but separate targets (usually) don't.
+    Only the address is hashed, even if the target is a slice or a `str`.
+    ```
+    # use std::collections::hash_map::DefaultHasher;
+    # use std::hash::Hasher;
+    # use std::sync::Arc;
+    # use pierce::Pierce;
+    fn ptr_hash<T: pierce::StableDeref>(p: &Pierce<T>) -> u64
+    where
+        T::Target: pierce::StableDeref,
+    {
+        let mut hasher = DefaultHasher::new();
+        p.hash_by_ptr(&mut hasher);
+        hasher.finish()
+    }
+    let arc = Arc::new(vec![1, 2, 3]);
+    let a = Pierce::new(arc.clone());
+    let b = Pierce::new(arc);
+    assert_eq!(ptr_hash(&a), ptr_hash(&b));
+    ```
+     */
+    #[inline]
+    pub fn hash_by_ptr<H: Hasher>(&self, state: &mut H) {
+        (self.target.as_ptr() as *const () as usize).hash(state)
+    }
+
+    /** Hint the CPU to bring the target into the L1 cache.
+
+    This is for hot loops where you know which targets you'll read next.
+    It issues `prefetcht0` on x86_64 and `prfm pldl1keep` on aarch64, and does nothing on other architectures.
+    Prefetching never faults, and has no effect on correctness.
+    ```
+    # use pierce::Pierce;
+    let pierces: Vec<_> = (0..4).map(|i| Pierce::new(Box::new(Box::new(i)))).collect();
+    let mut sum = 0;
+    for (i, p) in pierces.iter().enumerate() {
+        if let Some(next) = pierces.get(i + 1) {
+            next.prefetch_target();
+        }
+        sum += **p;
+    }
+    assert_eq!(sum, 6);
+    ```
+     */
+    #[inline(always)]
+    pub fn prefetch_target(&self) {
+        let ptr = self.target.as_ptr() as *const u8;
+        #[cfg(target_arch = "x86_64")]
+        unsafe {
+            core::arch::x86_64::_mm_prefetch::<{ core::arch::x86_64::_MM_HINT_T0 }>(
+                ptr as *const i8,
+            )
+        };
+        #[cfg(target_arch = "aarch64")]
+        unsafe {
+            core::arch::asm!("prfm pldl1keep, [{}]", in(reg) ptr, options(nostack, preserves_flags, readonly))
+        };
+        #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+        let _ = ptr;
+        // SAFETY: A prefetch is only a hint. It never faults or changes memory, whatever the address.
+    }
+
+    /** Check that the cached target is still where deref-ing the outer pointer twice leads.
+
+    This is always true for correct [`StableDeref`] implementations.
+    It is for debugging pointer types suspected of breaking the `StableDeref` contract,
+    by deref-ing to different places at different times.
+    The addresses are compared, not the values.
+    Debug builds check this every time a Pierce is deref-ed, and panic if it fails.
+    ```
+    # use pierce::Pierce;
+    let p = Pierce::new(Box::new(vec![1, 2, 3]));
+    assert!(p.is_cache_valid());
+    ```
+     */
+    #[inline]
+    pub fn is_cache_valid(&self) -> bool {
+        let fresh: &<T::Target as Deref>::Target = self.outer.deref().deref();
+        core::ptr::eq(self.target.as_ptr(), fresh)
+    }
 }
 
-unsafe impl<T> Send for Pierce<T>
+/** Find the Pierces whose cache doesn't match a fresh double-deref, and return their indices.
+
+This calls [`Pierce::is_cache_valid`] on every element, so it derefs every outer pointer twice.
+The indices are in order, and the returned `Vec` doesn't allocate if every cache is valid.
+```
+# use pierce::{verify_cache_integrity, Pierce};
+let pierces: Vec<_> = (0..4).map(|i| Pierce::new(Box::new(Box::new(i)))).collect();
+assert!(verify_cache_integrity(&pierces).is_empty());
+```
+*/
+#[cfg(feature = "alloc")]
+pub fn verify_cache_integrity<T>(pierces: &[Pierce<T>]) -> alloc::vec::Vec<usize>
 where
-    T: StableDeref + Send,
+    T: StableDeref,
     T::Target: StableDeref,
-    <T::Target as Deref>::Target: Sync,
 {
+    pierces
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| !p.is_cache_valid())
+        .map(|(i, _)| i)
+        .collect()
 }
 
-unsafe impl<T> Sync for Pierce<T>
+unsafe impl<T> StableDeref for Pierce<T>
 where
-    T: StableDeref + Sync,
+    T: StableDeref,
     T::Target: StableDeref,
-    <T::Target as Deref>::Target: Sync,
 {
 }
 
-unsafe impl<T> StableDeref for Pierce<T>
+/* Pierce never relies on its own address. The cached pointer points into the heap (or wherever the inner pointer points),
+not into the Pierce, and `StableDeref` guarantees moving the outer pointer doesn't move that target.
+So a Pierce can be moved out of a `Pin` whenever its outer pointer can.
+This is what the auto trait gives today, but spelling it out keeps it that way if private fields are added. */
+impl<T> Unpin for Pierce<T>
 where
-    T: StableDeref,
+    T: StableDeref + Unpin,
+    T::Target: StableDeref,
+{
+}
+
+// A Pierce must stay Unpin even when its target isn't.
+#[cfg(feature = "alloc")]
+const _: fn() = || {
+    fn assert_unpin<U: Unpin>() {}
+    struct Pinned(core::marker::PhantomPinned);
+    assert_unpin::<Pierce<alloc::boxed::Box<alloc::boxed::Box<Pinned>>>>();
+    assert_unpin::<Pierce<alloc::sync::Arc<alloc::vec::Vec<Pinned>>>>();
+    assert_unpin::<Pierce<&'static &'static core::marker::PhantomPinned>>();
+};
+
+/* The cached pointer only gives out `&Target`, which a shared borrow of the outer pointer gives out too.
+So a Pierce is as unwind safe as its outer pointer. The auto traits would instead require `Target: RefUnwindSafe`
+because of the raw pointer, even for outer pointers like `Arc` that already account for it. */
+impl<T> UnwindSafe for Pierce<T>
+where
+    T: StableDeref + UnwindSafe,
+    T::Target: StableDeref,
+{
+}
+
+impl<T> RefUnwindSafe for Pierce<T>
+where
+    T: StableDeref + RefUnwindSafe,
     T::Target: StableDeref,
 {
 }
@@ -254,42 +606,209 @@ where
     }
 }
 
+impl<T> Pierce<T>
+where
+    T: CloneStableDeref,
+    T::Target: StableDeref,
+{
+    /** Clone the Pierce, reusing the cached target instead of deref-ing the clone.
+
+    Clones of a [`CloneStableDeref`] pointer, like `Rc` and `Arc`, deref to the same inner pointer,
+    so they have the same target.
+    [`clone`][Clone::clone] can't take advantage of this, since it is also used for pointers like `Box`,
+    whose clones are new allocations.
+    ```
+    # use std::sync::Arc;
+    # use pierce::Pierce;
+    let p = Pierce::new(Arc::new(vec![1, 2, 3]));
+    let q = p.clone_cached();
+    assert!(std::ptr::eq(&*p, &*q));
+    ```
+     */
+    #[inline]
+    pub fn clone_cached(&self) -> Self {
+        Self {
+            outer: self.outer.clone(),
+            target: self.target,
+        }
+        /* SAFETY:
+        `CloneStableDeref` promises the clone derefs to the same inner pointer as `self.outer`.
+        That inner pointer derefs to our cached target, and stays alive as long as either outer pointer does.
+        */
+    }
+}
+
 impl<T> Deref for Pierce<T>
 where
     T: StableDeref,
     T::Target: StableDeref,
 {
     type Target = <T::Target as Deref>::Target;
-    #[inline]
+    #[inline(always)]
     fn deref(&self) -> &Self::Target {
-        unsafe { self.target.as_ref() }
-        /* SAFETY:
-        The Pierce must still be alive (not dropped) when this is called,
-        and thus the outer pointer must still be alive.
+        debug_assert!(
+            self.is_cache_valid(),
+            "the outer pointer no longer derefs to the cached target; is its StableDeref impl correct?"
+        );
+        unsafe { self.target.get() }
+        // SAFETY: The cache was made from `self.outer`, which is alive as long as `self` is.
+    }
+}
 
-        The Pierce might be moved, but it must be StableDeref so moving is ok.
+/** Forward `as_ref` to the target.
 
-        The inner pointer (which is the deref result of the outer pointer) must last as long as the outer pointer,
-        so it must still be alive too.
+A Pierce is `AsRef<U>` whenever its target is, so a `Pierce<Arc<String>>` (whose target is `str`)
+is `AsRef<str>`, `AsRef<[u8]>`, `AsRef<Path>` and `AsRef<OsStr>`, just like `str`.
+```
+# use std::{ffi::OsStr, path::{Path, PathBuf}, sync::Arc};
+# use pierce::Pierce;
+let name = Pierce::new(Arc::new(String::from("Cargo.toml")));
+let path: &Path = name.as_ref();
+let os: &OsStr = name.as_ref();
+assert_eq!(path, Path::new("Cargo.toml"));
+assert_eq!(os, "Cargo.toml");
+
+let dir = Pierce::new(Box::new(PathBuf::from("src")));
+assert_eq!(AsRef::<OsStr>::as_ref(&dir), "src");
+```
+
+Unsized targets like `str`, slices and `Path` are all `AsRef` themselves.
+For a target that isn't, like an integer, use deref (`&*pierce`) instead.
+*/
+impl<T, U> AsRef<U> for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: AsRef<U>,
+    U: ?Sized,
+{
+    #[inline]
+    fn as_ref(&self) -> &U {
+        (**self).as_ref()
+    }
+}
 
-        The target (which is the deref result of the inner pointer) must last as long as the inner pointer,
-        so it must still be alive too.
+/** Format the target. The outer and inner pointers are not shown.
 
-        It might seem that interior mutability can cause an issue,
-        but it actually is impossible to get long-living reference out of a RefCell or Mutex,
-        so you can't deref to anything inside an interior-mutable cell anyway.
-        */
+`{:?}` prints just the target, like `Box` does.
+`{:#?}` prints a `Pierce` struct with the target and the address it is cached at.
+
+Like `Box<T>`, a `Pierce` is only `Debug` if its target is.
+*/
+impl<T> fmt::Debug for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            f.debug_struct("Pierce")
+                .field("target", &&**self)
+                .field("cached_at", &format_args!("{:p}", self.target.as_ptr()))
+                .finish()
+        } else {
+            fmt::Debug::fmt(&**self, f)
+        }
     }
 }
 
-impl<T> AsRef<<T::Target as Deref>::Target> for Pierce<T>
+/** Format the target, passing formatting flags like width and precision through.
+
+```
+# use std::rc::Rc;
+# use pierce::Pierce;
+let p = Pierce::new(Rc::new(String::from("hello")));
+assert_eq!(format!("{p}"), "hello");
+assert_eq!(p.to_string(), "hello");
+```
+*/
+impl<T> fmt::Display for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+/** Format the address of the cached target.
+
+This is the address the Pierce derefs to.
+It is *not* what `{:p}` prints for the outer pointer:
+for a `Pierce<Arc<Vec<u8>>>`, formatting the `Arc` with `{:p}` shows where the `Vec` is,
+while formatting the Pierce shows where the `Vec`'s elements are.
+Only the address is printed, even if the target is a slice or a `str`.
+
+```
+# use std::sync::Arc;
+# use pierce::Pierce;
+let p = Pierce::new(Arc::new(vec![1u8, 2, 3]));
+assert_eq!(format!("{:p}", p), format!("{:p}", p.as_ptr()));
+assert_ne!(format!("{:p}", p), format!("{:p}", p.borrow_outer()));
+```
+*/
+impl<T> fmt::Pointer for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&(self.target.as_ptr() as *const ()), f)
+    }
+}
+
+// The numeric formatting traits just format the target, like `Display`.
+macro_rules! forward_fmt {
+    ($($fmt_trait:ident),*) => {$(
+        impl<T> fmt::$fmt_trait for Pierce<T>
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            <T::Target as Deref>::Target: fmt::$fmt_trait,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::$fmt_trait::fmt(&**self, f)
+            }
+        }
+    )*};
+}
+
+forward_fmt!(LowerHex, UpperHex, Binary, Octal, LowerExp, UpperExp);
+
+/** Forward to the target error, including its [`source`][std::error::Error::source].
+
+A shared error like `Pierce<Arc<Box<dyn Error + Send + Sync>>>` can then be used as an error itself,
+and converted into a `Box<dyn Error>` with `?`.
+*/
+#[cfg(feature = "std")]
+impl<T> std::error::Error for Pierce<T>
 where
     T: StableDeref,
     T::Target: StableDeref,
+    <T::Target as Deref>::Target: std::error::Error,
 {
     #[inline]
-    fn as_ref(&self) -> &<T::Target as Deref>::Target {
-        &**self
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        (**self).source()
+    }
+}
+
+/** Resolve the target as socket addresses, so a pierced address string can be passed to `TcpStream::connect` and the like.
+*/
+#[cfg(feature = "std")]
+impl<T> std::net::ToSocketAddrs for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: std::net::ToSocketAddrs,
+{
+    type Iter = <<T::Target as Deref>::Target as std::net::ToSocketAddrs>::Iter;
+    #[inline]
+    fn to_socket_addrs(&self) -> std::io::Result<Self::Iter> {
+        (**self).to_socket_addrs()
     }
 }
 
@@ -303,11 +822,103 @@ where
     }
 }
 
+/** Parse the inner pointer, wrap it up into the outer pointer, then cache.
+
+Outer pointers like `Box`, `Rc` and `Arc` don't implement `FromStr` themselves,
+so the inner pointer is parsed and converted with `From`. Parse errors are returned unchanged.
+
+```
+# use pierce::Pierce;
+let p: Pierce<Box<String>> = "hello".parse().unwrap();
+assert_eq!(p, "hello");
+```
+
+```
+# use std::{ops::Deref, rc::Rc, str::FromStr};
+# use pierce::{Pierce, StableDeref};
+struct Name(String);
+impl Deref for Name {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+unsafe impl StableDeref for Name {}
+impl FromStr for Name {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            Err("empty name")
+        } else {
+            Ok(Name(s.into()))
+        }
+    }
+}
+let name: Pierce<Rc<Name>> = "alice".parse().unwrap();
+assert_eq!(name, "alice");
+assert_eq!("".parse::<Pierce<Rc<Name>>>().unwrap_err(), "empty name");
+```
+*/
+impl<T> FromStr for Pierce<T>
+where
+    T: StableDeref + From<<T as Deref>::Target>,
+    T::Target: StableDeref + FromStr + Sized,
+{
+    type Err = <T::Target as FromStr>::Err;
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::new(T::from(s.parse()?)))
+    }
+}
+
+impl<T> Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    for<'a> &'a <T::Target as Deref>::Target: IntoIterator,
+{
+    /** Iterate over the target.
+
+    This is available when a reference to the target can be iterated, like with slices and maps.
+    It returns the same iterator as iterating over `&pierce`.
+    ```
+    # use pierce::Pierce;
+    let pierce = Pierce::new(Box::new(vec![-1, 2, 3]));
+    let positive: Vec<i32> = pierce.iter().filter(|x| **x > 0).copied().collect();
+    assert_eq!(positive, [2, 3]);
+    ```
+     */
+    #[inline]
+    pub fn iter(&self) -> <&<T::Target as Deref>::Target as IntoIterator>::IntoIter {
+        (**self).into_iter()
+    }
+}
+
+/** Iterate over the target, so `for x in &pierce` works.
+
+This gives exactly the iterator of `&target`, for slices, maps, sets and anything else with an `IntoIterator` reference.
+`&str` isn't `IntoIterator`, so for string targets call [`str::chars`] or [`str::bytes`] through deref instead.
+*/
+impl<'a, T> IntoIterator for &'a Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    &'a <T::Target as Deref>::Target: IntoIterator,
+{
+    type Item = <&'a <T::Target as Deref>::Target as IntoIterator>::Item;
+    type IntoIter = <&'a <T::Target as Deref>::Target as IntoIterator>::IntoIter;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        (**self).into_iter()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::arc_with_non_send_sync)]
     fn test_arc_vec() {
         use std::cell::RefCell;
         use std::ops::AddAssign;
@@ -317,8 +928,8 @@ mod tests {
         let a = Arc::new(v);
         let p1 = Pierce::new(a);
         let p2 = p1.clone();
-        p1.get(0).unwrap().borrow_mut().add_assign(5);
-        assert_eq!(*p2.get(0).unwrap().borrow(), 6);
+        p1.first().unwrap().borrow_mut().add_assign(5);
+        assert_eq!(*p2.first().unwrap().borrow(), 6);
     }
 
     #[test]
@@ -348,6 +959,193 @@ mod tests {
         assert_eq!(*Pierce::deref(&pierce_twice), 42);
     }
 
+    #[test]
+    fn test_clone_cached() {
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let p = Pierce::new(Arc::new(String::from("hello")));
+        let q = p.clone_cached();
+        drop(p);
+        assert_eq!(&*q, "hello");
+        assert!(std::ptr::eq(&*q, &***q.borrow_outer()));
+
+        let inner = Box::new([1, 2, 3]);
+        let r = Pierce::new(Rc::new(inner));
+        assert_eq!(r.clone_cached(), [1, 2, 3]);
+        assert_eq!(Rc::strong_count(r.borrow_outer()), 1);
+    }
+
+    #[test]
+    fn test_new_tracks_caller() {
+        use std::cell::Cell;
+        use std::panic::Location;
+
+        // Remembers where it was deref-ed from.
+        struct Tracked(Box<i32>, Cell<Option<&'static Location<'static>>>);
+        impl Deref for Tracked {
+            type Target = Box<i32>;
+            #[track_caller]
+            fn deref(&self) -> &Box<i32> {
+                self.1.set(Some(Location::caller()));
+                &self.0
+            }
+        }
+        unsafe impl StableDeref for Tracked {}
+
+        let line = line!() + 1;
+        let p = Pierce::new(Tracked(Box::new(1), Cell::new(None)));
+        let location = p.borrow_outer().1.get().unwrap();
+        assert_eq!((location.file(), location.line()), (file!(), line));
+    }
+
+    #[test]
+    fn test_drop_order() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        type Log = Rc<RefCell<Vec<&'static str>>>;
+        struct Target(Log);
+        impl Drop for Target {
+            fn drop(&mut self) {
+                self.0.borrow_mut().push("target");
+            }
+        }
+        struct Inner(Box<Target>, Log);
+        impl Deref for Inner {
+            type Target = Target;
+            fn deref(&self) -> &Target {
+                &self.0
+            }
+        }
+        impl Drop for Inner {
+            fn drop(&mut self) {
+                self.1.borrow_mut().push("inner");
+            }
+        }
+        unsafe impl StableDeref for Inner {}
+
+        let log = Log::default();
+        let new_inner = || Inner(Box::new(Target(log.clone())), log.clone());
+
+        // The inner pointer's drop runs first, then its fields, including the target.
+        let p = Pierce::new(Box::new(new_inner()));
+        assert!(log.borrow().is_empty());
+        drop(p);
+        assert_eq!(*log.borrow(), ["inner", "target"]);
+
+        // Taking the outer pointer out drops nothing.
+        log.borrow_mut().clear();
+        let outer = Pierce::new(Box::new(new_inner())).into_outer();
+        assert!(log.borrow().is_empty());
+        drop(outer);
+        assert_eq!(*log.borrow(), ["inner", "target"]);
+
+        // A shared outer pointer drops the inner pointer with its last owner.
+        log.borrow_mut().clear();
+        let a = Pierce::new(Rc::new(new_inner()));
+        let b = a.clone();
+        drop(a);
+        assert!(log.borrow().is_empty());
+        assert!(b.is_cache_valid());
+        drop(b);
+        assert_eq!(*log.borrow(), ["inner", "target"]);
+    }
+
+    #[test]
+    fn test_verify_cache_integrity() {
+        use std::cell::Cell;
+
+        // Breaks the StableDeref contract by switching between two boxes.
+        struct Flip {
+            boxes: [Box<i32>; 2],
+            second: Cell<bool>,
+        }
+        impl Deref for Flip {
+            type Target = Box<i32>;
+            fn deref(&self) -> &Box<i32> {
+                &self.boxes[self.second.get() as usize]
+            }
+        }
+        unsafe impl StableDeref for Flip {}
+
+        let pierces: Vec<_> = (0..5)
+            .map(|i| {
+                Pierce::new(Box::new(Flip {
+                    boxes: [Box::new(i), Box::new(-i)],
+                    second: Cell::new(false),
+                }))
+            })
+            .collect();
+        assert!(pierces.iter().all(Pierce::is_cache_valid));
+        assert!(verify_cache_integrity(&pierces).is_empty());
+
+        pierces[1].borrow_outer().second.set(true);
+        pierces[4].borrow_outer().second.set(true);
+        assert_eq!(verify_cache_integrity(&pierces), [1, 4]);
+        pierces[4].borrow_outer().second.set(false);
+        assert_eq!(verify_cache_integrity(&pierces), [1]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the outer pointer no longer derefs to the cached target")]
+    fn test_deref_checks_cache() {
+        use std::cell::Cell;
+
+        // Breaks the StableDeref contract by switching to a new box after the first deref.
+        struct Weird {
+            boxes: [Box<i32>; 2],
+            derefs: Cell<usize>,
+        }
+        impl Deref for Weird {
+            type Target = Box<i32>;
+            fn deref(&self) -> &Box<i32> {
+                let n = self.derefs.get();
+                self.derefs.set(n + 1);
+                &self.boxes[(n > 0) as usize]
+            }
+        }
+        unsafe impl StableDeref for Weird {}
+
+        let p = Pierce::new(Box::new(Weird {
+            boxes: [Box::new(1), Box::new(2)],
+            derefs: Cell::new(0),
+        }));
+        let _ = *p;
+    }
+
+    #[test]
+    fn test_unpin() {
+        use std::pin::Pin;
+
+        let mut p = Pierce::new(Box::new(vec![1, 2, 3]));
+        let pinned = Pin::new(&mut p);
+        let moved: Pierce<Box<Vec<i32>>> = std::mem::take(Pin::into_inner(pinned));
+        assert_eq!(moved, [1, 2, 3]);
+        assert!(p.is_empty());
+    }
+
+    #[test]
+    #[allow(clippy::redundant_allocation)]
+    fn test_unwind_safe() {
+        use std::panic::{catch_unwind, RefUnwindSafe, UnwindSafe};
+        use std::rc::Rc;
+        use std::sync::{Arc, Mutex};
+
+        fn assert_unwind_safe<P: UnwindSafe + RefUnwindSafe>() {}
+        assert_unwind_safe::<Arc<Vec<u8>>>();
+        assert_unwind_safe::<Pierce<Arc<Vec<u8>>>>();
+        assert_unwind_safe::<Pierce<Rc<String>>>();
+        assert_unwind_safe::<Pierce<Box<Box<u32>>>>();
+        assert_unwind_safe::<Pierce<Arc<Box<Mutex<u32>>>>>();
+        assert_unwind_safe::<Pierce<&&str>>();
+
+        let pierce = Pierce::new(Arc::new(vec![1u8, 2, 3]));
+        let sum = catch_unwind(|| pierce.iter().map(|&x| x as u32).sum::<u32>()).unwrap();
+        assert_eq!(sum, 6);
+    }
+
     #[test]
     fn test_send() {
         use std::sync::Arc;
@@ -363,6 +1161,94 @@ mod tests {
         h2.join().unwrap();
     }
     #[test]
+    fn test_send_sync_match_outer() {
+        use std::cell::{Cell, RefCell};
+        use std::marker::PhantomData;
+        use std::pin::Pin;
+        use std::rc::Rc;
+        use std::sync::{Arc, Mutex};
+
+        // Inherent associated consts win over trait ones, but only apply when the bound holds.
+        struct Check<P>(PhantomData<P>);
+        trait Fallback {
+            const SEND: bool = false;
+            const SYNC: bool = false;
+        }
+        impl<P> Fallback for Check<P> {}
+        impl<P: Send> Check<P> {
+            const SEND: bool = true;
+        }
+        impl<P: Sync> Check<P> {
+            const SYNC: bool = true;
+        }
+        macro_rules! assert_same {
+            ($wrapped:ty, $outer:ty) => {
+                assert_eq!(
+                    Check::<$wrapped>::SEND,
+                    Check::<$outer>::SEND,
+                    "Send for {}",
+                    stringify!($wrapped)
+                );
+                assert_eq!(
+                    Check::<$wrapped>::SYNC,
+                    Check::<$outer>::SYNC,
+                    "Sync for {}",
+                    stringify!($wrapped)
+                );
+            };
+        }
+        // The other caching types follow the same rule as Pierce.
+        macro_rules! assert_same_as_outer {
+            ($($outer:ty),*) => {$(
+                assert_same!(Pierce<$outer>, $outer);
+                assert_same!(PierceVec<$outer>, $outer);
+                assert_same!(PierceArray<$outer, 2>, $outer);
+                assert_same!(PiercePair<$outer, $outer>, $outer);
+            )*};
+        }
+        struct RawPtr(#[allow(dead_code)] *const u8);
+        assert_same_as_outer!(
+            Box<Box<Cell<u32>>>,
+            Box<Box<RefCell<u32>>>,
+            Box<Vec<Cell<u32>>>,
+            Arc<Box<Cell<u32>>>,
+            Arc<Box<Mutex<u32>>>,
+            Box<Rc<u32>>,
+            Rc<Box<u32>>,
+            Box<Box<RawPtr>>,
+            Arc<Vec<RawPtr>>,
+            Box<Box<u32>>,
+            Arc<String>
+        );
+        assert_same!(PierceC<Box<Box<Cell<u32>>>>, Box<Box<Cell<u32>>>);
+        assert_same!(PierceC<Rc<Box<u32>>>, Rc<Box<u32>>);
+        assert_same!(PierceCopy<&Box<Cell<u32>>>, &Box<Cell<u32>>);
+        assert_same!(PierceCopy<&Box<Mutex<u32>>>, &Box<Mutex<u32>>);
+        assert_same!(
+            PinnedPierce<Box<Pin<Box<Cell<u32>>>>>,
+            Box<Pin<Box<Cell<u32>>>>
+        );
+        assert_same!(PinnedPierce<Rc<Pin<Box<u32>>>>, Rc<Pin<Box<u32>>>);
+        const { assert!(Check::<Pierce<Box<Box<Cell<u32>>>>>::SEND) };
+        const { assert!(Check::<PierceVec<Box<Box<Cell<u32>>>>>::SEND) };
+        const { assert!(!Check::<Pierce<Rc<Box<u32>>>>::SEND) };
+        // PierceCell is never Sync, like RefCell, but Send like the others.
+        const { assert!(Check::<PierceCell<Box<Box<Cell<u32>>>>>::SEND) };
+        const { assert!(!Check::<PierceCell<Rc<Box<u32>>>>::SEND) };
+        const { assert!(!Check::<PierceCell<Box<Box<u32>>>>::SYNC) };
+
+        let p = Pierce::new(Box::new(Box::new(Cell::new(1))));
+        let moved = std::thread::spawn(move || {
+            p.set(p.get() + 1);
+            p
+        })
+        .join()
+        .unwrap();
+        assert_eq!(moved.get(), 2);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "leaks on purpose, see miri_tests::test_sync_scoped")]
     fn test_sync() {
         let p: Pierce<Box<String>> = Pierce::new(Box::new(String::from("hello world")));
         let p1: &'static Pierce<Box<String>> = Box::leak(Box::new(p));
@@ -396,4 +1282,304 @@ mod tests {
         inner_test::<Arc<Vec<i32>>>();
         inner_test::<Box<Arc<i32>>>();
     }
+
+    #[test]
+    fn test_iter() {
+        use std::collections::HashMap;
+
+        let pierce = Pierce::new(Box::new(vec![1, 2, 3]));
+        let mut sum = 0;
+        for x in &pierce {
+            sum += x;
+        }
+        assert_eq!(sum, 6);
+        assert!(pierce.iter().eq([1, 2, 3].iter()));
+
+        let map: HashMap<&str, i32> = HashMap::from([("a", 1), ("b", 2)]);
+        let pierce = Pierce::new(Box::new(Box::new(map)));
+        let mut pairs: Vec<(&&str, &i32)> = pierce.iter().collect();
+        pairs.sort();
+        assert_eq!(pairs, [(&"a", &1), (&"b", &2)]);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let pierce = Pierce::new(Arc::new(vec![1, 2, 3]));
+        let iter = (&pierce).into_iter();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        assert_eq!(iter.len(), 3);
+        let mut seen = Vec::new();
+        for x in &pierce {
+            seen.push(*x);
+        }
+        assert_eq!(seen, [1, 2, 3]);
+
+        let map: HashMap<&str, i32> = HashMap::from([("a", 1), ("b", 2)]);
+        let pierce = Pierce::new(Box::new(Box::new(map)));
+        assert_eq!((&pierce).into_iter().size_hint(), (2, Some(2)));
+        let mut sum = 0;
+        for (_, v) in &pierce {
+            sum += v;
+        }
+        assert_eq!(sum, 3);
+    }
+
+    #[test]
+    fn test_iter_falls_back_to_target_method() {
+        struct Bag(Vec<u8>);
+        impl Bag {
+            fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+                self.0.iter().map(|x| x * 2)
+            }
+        }
+        let pierce = Pierce::new(Box::new(Box::new(Bag(vec![1, 2]))));
+        assert!(pierce.iter().eq([2, 4]));
+    }
+
+    #[test]
+    fn test_hash_by_ptr() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::sync::Arc;
+
+        fn ptr_hash<T: StableDeref>(p: &Pierce<T>) -> u64
+        where
+            T::Target: StableDeref,
+        {
+            let mut hasher = DefaultHasher::new();
+            p.hash_by_ptr(&mut hasher);
+            hasher.finish()
+        }
+        let arc = Arc::new(vec![1, 2, 3]);
+        let a = Pierce::new(arc.clone());
+        let b = Pierce::new(arc);
+        let c = Pierce::new(Arc::new(vec![1, 2, 3]));
+        assert_eq!(ptr_hash(&a), ptr_hash(&b));
+        assert_ne!(ptr_hash(&a), ptr_hash(&c));
+
+        let mut hasher = DefaultHasher::new();
+        (c.as_ptr() as usize).hash(&mut hasher);
+        assert_eq!(ptr_hash(&c), hasher.finish());
+    }
+
+    #[test]
+    fn test_prefetch_target() {
+        let p = Pierce::new(Box::new(vec![1u8; 100]));
+        p.prefetch_target();
+        assert_eq!(p[99], 1);
+        // Zero-sized targets have a dangling address, which is fine to prefetch.
+        let empty = Pierce::new(Box::new(Vec::<u64>::new()));
+        empty.prefetch_target();
+    }
+
+    #[test]
+    fn test_display() {
+        let p = Pierce::new(Box::new(Box::new(1.23456f64)));
+        assert_eq!(format!("{:>10.3}", p), format!("{:>10.3}", 1.23456f64));
+        assert_eq!(format!("{:>10.3}", p), "     1.235");
+        assert_eq!(
+            format!("{:*<8}", Pierce::new(Box::new(String::from("ab")))),
+            "ab******"
+        );
+    }
+
+    #[test]
+    fn test_numeric_fmt() {
+        use std::sync::Arc;
+
+        let value = 0xbeefu64;
+        let p = Pierce::new(Arc::new(Box::new(value)));
+        assert_eq!(format!("{:x}", p), "beef");
+        assert_eq!(format!("{:#010x}", p), format!("{:#010x}", value));
+        assert_eq!(format!("{:#010X}", p), format!("{:#010X}", value));
+        assert_eq!(format!("{:#010o}", p), format!("{:#010o}", value));
+        assert_eq!(format!("{:#034b}", p), format!("{:#034b}", value));
+        assert_eq!(format!("{:>12e}", p), format!("{:>12e}", value));
+        assert_eq!(format!("{:<12E}", p), format!("{:<12E}", value));
+        assert_eq!(format!("{:b}", Pierce::new(Box::new(Box::new(5u8)))), "101");
+        assert_eq!(format!("{:o}", Pierce::new(Box::new(Box::new(8u8)))), "10");
+    }
+
+    #[test]
+    fn test_exp_fmt() {
+        let p = Pierce::new(Box::new(Box::new(1234.5f64)));
+        assert_eq!(format!("{:e}", p), format!("{:e}", ***p.borrow_outer()));
+        assert_eq!(format!("{:e}", p), "1.2345e3");
+        assert_eq!(format!("{:.2E}", p), "1.23E3");
+    }
+
+    #[test]
+    fn test_error() {
+        use std::error::Error;
+        use std::sync::Arc;
+
+        #[derive(Debug)]
+        struct Layer(&'static str, Option<Box<dyn Error + Send + Sync>>);
+        impl fmt::Display for Layer {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.0)
+            }
+        }
+        impl Error for Layer {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                self.1.as_ref().map(|e| &**e as _)
+            }
+        }
+
+        let low = Layer("low", None);
+        let mid = Layer("mid", Some(Box::new(low)));
+        let top: Box<dyn Error + Send + Sync> = Box::new(Layer("top", Some(Box::new(mid))));
+        let pierce = Pierce::new(Arc::new(top));
+        let shared = pierce.clone();
+
+        let mut chain = Vec::new();
+        let mut current: Option<&(dyn Error + 'static)> = Some(&pierce);
+        while let Some(e) = current {
+            chain.push(e.to_string());
+            current = e.source();
+        }
+        assert_eq!(chain, ["top", "mid", "low"]);
+        let mid = pierce.source().unwrap().downcast_ref::<Layer>().unwrap();
+        assert_eq!(mid.0, "mid");
+
+        fn fails(e: Pierce<Arc<Box<dyn Error + Send + Sync>>>) -> Result<(), Box<dyn Error>> {
+            Err(e)?
+        }
+        let boxed = fails(shared).unwrap_err();
+        assert_eq!(boxed.to_string(), "top");
+        assert_eq!(boxed.source().unwrap().to_string(), "mid");
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "Miri doesn't support networking")]
+    fn test_to_socket_addrs() {
+        use std::net::{SocketAddr, TcpListener, ToSocketAddrs};
+        use std::sync::Arc;
+
+        let endpoint = Pierce::new(Arc::new(String::from("127.0.0.1:8080")));
+        let addrs: Vec<SocketAddr> = endpoint.to_socket_addrs().unwrap().collect();
+        assert_eq!(addrs, ["127.0.0.1:8080".parse().unwrap()]);
+
+        let any_port = Pierce::new(Box::new(String::from("127.0.0.1:0")));
+        let listener = TcpListener::bind(&any_port).unwrap();
+        assert!(listener.local_addr().unwrap().ip().is_loopback());
+    }
+
+    #[test]
+    fn test_as_ref() {
+        use std::ffi::{CStr, CString, OsStr};
+        use std::path::{Path, PathBuf};
+        use std::sync::Arc;
+
+        let path = Pierce::new(Arc::new(Box::new(PathBuf::from("/tmp"))));
+        let as_path: &Path = path.as_ref();
+        assert!(std::ptr::eq(as_path, &**path));
+        let as_os: &OsStr = path.as_ref();
+        assert_eq!(as_os, "/tmp");
+
+        let c = Pierce::new(Box::new(CString::new("hi").unwrap()));
+        let as_c: &CStr = c.as_ref();
+        assert_eq!(as_c.to_bytes(), b"hi");
+
+        let bytes = Pierce::new(Box::new(String::from("abc")));
+        let as_bytes: &[u8] = bytes.as_ref();
+        assert_eq!(as_bytes, b"abc");
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "Miri isolates the file system")]
+    fn test_file_open() {
+        use std::fs::File;
+        use std::path::PathBuf;
+        use std::sync::Arc;
+
+        let name = Pierce::new(Arc::new(String::from("Cargo.toml")));
+        File::open(&name).unwrap();
+        let path = Pierce::new(Box::new(PathBuf::from("Cargo.toml")));
+        File::open(&path).unwrap();
+        assert!(std::fs::metadata(path).unwrap().is_file());
+    }
+
+    #[test]
+    fn test_pointer_fmt() {
+        use std::sync::Arc;
+
+        let p1 = Pierce::new(Arc::new(vec![1u8, 2, 3]));
+        let p2 = p1.clone();
+        assert_eq!(format!("{:p}", p1), format!("{:p}", p2));
+        let p3 = Pierce::new(Arc::new(vec![1u8, 2, 3]));
+        assert_ne!(format!("{:p}", p1), format!("{:p}", p3));
+    }
+
+    #[test]
+    fn test_debug() {
+        use std::sync::Arc;
+
+        let pierce = Pierce::new(Arc::new(vec![1, 2]));
+        assert_eq!(format!("{:?}", pierce), "[1, 2]");
+        let expected = format!(
+            "Pierce {{\n    target: [\n        1,\n        2,\n    ],\n    cached_at: {:p},\n}}",
+            &*pierce
+        );
+        assert_eq!(format!("{:#?}", pierce), expected);
+
+        #[derive(Debug)]
+        struct Holder {
+            _name: Pierce<std::rc::Rc<String>>,
+        }
+        let holder = Holder {
+            _name: Pierce::new(std::rc::Rc::new(String::from("a"))),
+        };
+        assert_eq!(format!("{:?}", holder), "Holder { _name: \"a\" }");
+    }
+}
+
+/* Tests that check Pierce's safety properties under Miri (`cargo +nightly miri test`).
+The tests above that break Miri's model on purpose (like leaking) are ignored there, and replaced here. */
+#[cfg(all(test, miri))]
+mod miri_tests {
+    use super::*;
+    use std::{rc::Rc, sync::Arc};
+
+    #[test]
+    fn test_sync_scoped() {
+        let p: Pierce<Box<String>> = Pierce::new(Box::new(String::from("hello world")));
+        std::thread::scope(|s| {
+            s.spawn(|| assert_eq!(&*p, "hello world"));
+            s.spawn(|| assert_eq!(&*p, "hello world"));
+        });
+    }
+
+    #[test]
+    fn test_cache_survives_moves() {
+        let p = Pierce::new(Box::new(vec![1u8, 2, 3]));
+        let moved = Box::new(p);
+        let mut v = vec![*moved];
+        v.reserve(100);
+        assert_eq!(&*v[0], &[1, 2, 3]);
+        let outer = v.pop().unwrap().into_outer();
+        assert_eq!(&**outer, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_clones_outlive_original() {
+        let p = Pierce::new(Rc::new(String::from("shared")));
+        let cloned = p.clone();
+        drop(p);
+        assert_eq!(&*cloned, "shared");
+        let a = Pierce::new(Arc::new(Box::new(5u64)));
+        let b = a.clone();
+        drop(a);
+        assert_eq!(*b, 5);
+    }
+
+    #[test]
+    fn test_zero_sized_target() {
+        let p = Pierce::new(Box::new(Box::new(())));
+        assert_eq!(*p, ());
+        let p = Pierce::new(Box::new(Vec::<u8>::new()));
+        assert!(p.is_empty());
+    }
 }
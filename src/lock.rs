@@ -0,0 +1,154 @@
+/*! Cached reads through [`Mutex`] and [`RwLock`].
+
+Reading `Arc<RwLock<Vec<T>>>` normally means locking, deref-ing the guard to the `Vec`, then deref-ing the `Vec`.
+Lock guards are [`StableDeref`], so they can be wrapped in a [`Pierce`]:
+the double deref is done once when locking, and every access within the critical section is a single jump.
+
+```
+# use std::sync::RwLock;
+let lock = RwLock::new(vec![1, 2, 3]);
+let guard = pierce::lock::read(&lock).unwrap();
+assert_eq!(guard.iter().sum::<i32>(), 6);
+assert_eq!(guard[2], 3);
+```
+
+The returned Pierce borrows the lock, so it can't outlive it.
+To get a handle that keeps the lock alive by itself, use [`read_owned`] on an `Arc<RwLock<_>>`.
+*/
+
+use std::{
+    ops::Deref,
+    sync::{Arc, LockResult, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard},
+};
+
+use crate::{Pierce, StableDeref};
+
+#[inline]
+fn map_lock_result<G, U>(result: LockResult<G>, f: impl FnOnce(G) -> U) -> LockResult<U> {
+    match result {
+        Ok(guard) => Ok(f(guard)),
+        Err(poisoned) => Err(PoisonError::new(f(poisoned.into_inner()))),
+    }
+}
+
+/** Lock `lock` for reading, and cache the target of its content.
+
+Poisoning is reported just like [`RwLock::read`].
+*/
+#[inline]
+pub fn read<U>(lock: &RwLock<U>) -> LockResult<Pierce<RwLockReadGuard<'_, U>>>
+where
+    U: StableDeref + ?Sized,
+{
+    map_lock_result(lock.read(), Pierce::new)
+}
+
+/** Lock `mutex`, and cache the target of its content.
+
+Poisoning is reported just like [`Mutex::lock`].
+*/
+#[inline]
+pub fn lock<U>(mutex: &Mutex<U>) -> LockResult<Pierce<MutexGuard<'_, U>>>
+where
+    U: StableDeref + ?Sized,
+{
+    map_lock_result(mutex.lock(), Pierce::new)
+}
+
+/** Lock `lock` for reading, returning a handle that also owns the `Arc`.
+
+The lock is released when the handle is dropped.
+```
+# use std::sync::{Arc, RwLock};
+let lock = Arc::new(RwLock::new(String::from("hello")));
+let handle = pierce::lock::read_owned(lock.clone()).unwrap();
+drop(lock);
+assert_eq!(&*handle, "hello");
+```
+*/
+pub fn read_owned<U>(lock: Arc<RwLock<U>>) -> LockResult<OwnedReadPierce<U>>
+where
+    U: StableDeref + 'static,
+{
+    let guard = lock.read();
+    // SAFETY: The guard is stored along with the Arc, and is dropped before it. See `OwnedReadPierce`.
+    let guard: LockResult<RwLockReadGuard<'static, U>> = unsafe { std::mem::transmute(guard) };
+    map_lock_result(guard, |guard| OwnedReadPierce {
+        guard: Pierce::new(guard),
+        _lock: lock,
+    })
+}
+
+/** A read-locked [`RwLock`] that keeps its `Arc` alive. Derefs to the cached target.
+
+Created with [`read_owned`].
+*/
+pub struct OwnedReadPierce<U>
+where
+    U: StableDeref + 'static,
+{
+    /* The guard claims to borrow the lock forever, but it actually borrows the lock inside `_lock`.
+    The Arc keeps the lock at the same address. Fields are dropped in declaration order,
+    so the guard is released before the Arc is dropped. The guard is never handed out. */
+    guard: Pierce<RwLockReadGuard<'static, U>>,
+    _lock: Arc<RwLock<U>>,
+}
+
+impl<U> Deref for OwnedReadPierce<U>
+where
+    U: StableDeref + 'static,
+{
+    type Target = U::Target;
+    #[inline]
+    fn deref(&self) -> &U::Target {
+        &self.guard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_and_lock() {
+        let rw = RwLock::new(vec![1, 2, 3]);
+        {
+            let a = read(&rw).unwrap();
+            let b = read(&rw).unwrap();
+            assert_eq!(&*a as *const [i32], &*b as *const [i32]);
+            assert!(rw.try_write().is_err());
+        }
+        rw.write().unwrap().push(4);
+        assert_eq!(&*read(&rw).unwrap(), &[1, 2, 3, 4]);
+
+        let mutex = Mutex::new(String::from("hello"));
+        let guard = lock(&mutex).unwrap();
+        assert_eq!(&*guard, "hello");
+        assert!(mutex.try_lock().is_err());
+    }
+
+    #[test]
+    fn test_poisoned() {
+        let mutex = Arc::new(Mutex::new(vec![1]));
+        let cloned = mutex.clone();
+        let _ = std::thread::spawn(move || {
+            let _guard = cloned.lock();
+            panic!("poison");
+        })
+        .join();
+        let poisoned = lock(&mutex).unwrap_err();
+        assert_eq!(&**poisoned.get_ref(), &[1]);
+    }
+
+    #[test]
+    fn test_read_owned() {
+        let rw = Arc::new(RwLock::new(vec![1, 2, 3]));
+        let handle = read_owned(rw.clone()).unwrap();
+        assert!(rw.try_write().is_err());
+        assert_eq!(Arc::strong_count(&rw), 2);
+        assert_eq!(handle[1], 2);
+        drop(handle);
+        assert_eq!(Arc::strong_count(&rw), 1);
+        rw.write().unwrap().push(4);
+    }
+}
@@ -0,0 +1,166 @@
+use core::ops::Deref;
+
+use crate::{Cached, StableDeref};
+
+macro_rules! multi_pierce {
+    (
+        $(#[$meta:meta])*
+        $name:ident { $($ty:ident $outer:ident $target:ident $getter:ident),+ }
+    ) => {
+        $(#[$meta])*
+        pub struct $name<$($ty),+>
+        where
+            $(
+                $ty: StableDeref,
+                $ty::Target: StableDeref,
+            )+
+        {
+            $($outer: $ty,)+
+            $($target: Cached<$ty>,)+
+        }
+
+        impl<$($ty),+> $name<$($ty),+>
+        where
+            $(
+                $ty: StableDeref,
+                $ty::Target: StableDeref,
+            )+
+        {
+            // Rustdoc doesn't unindent block doc comments from macros, so each paragraph is its own comment.
+            /** Create a new multi-Pierce.
+             */
+            /** This method derefs each outer pointer twice and cache the address where each inner pointer points to, just like [`Pierce::new`][crate::Pierce::new].
+             */
+            #[inline]
+            pub fn new($($outer: $ty),+) -> Self {
+                $(
+                    let $target = Cached::new(&$outer);
+                )+
+                Self { $($outer,)+ $($target,)+ }
+            }
+
+            $(
+                /** Get the cached target of this slot.
+                 */
+                #[inline]
+                pub fn $getter(&self) -> &<$ty::Target as Deref>::Target {
+                    unsafe { self.$target.get() }
+                    // SAFETY: The cache of this slot was made from the slot's outer pointer, kept alive in `self`.
+                }
+            )+
+
+            /** Get the cached targets of all slots.
+             */
+            #[inline]
+            pub fn as_refs(&self) -> ($(&<$ty::Target as Deref>::Target,)+) {
+                ($(self.$getter(),)+)
+            }
+
+            /** Borrow the outer pointers of all slots.
+             */
+            #[inline]
+            pub fn borrow_outers(&self) -> ($(&$ty,)+) {
+                ($(&self.$outer,)+)
+            }
+
+            /** Get the outer pointers of all slots out.
+             */
+            #[inline]
+            pub fn into_outers(self) -> ($($ty,)+) {
+                ($(self.$outer,)+)
+            }
+        }
+
+        impl<$($ty),+> Clone for $name<$($ty),+>
+        where
+            $(
+                $ty: StableDeref + Clone,
+                $ty::Target: StableDeref,
+            )+
+        {
+            #[inline]
+            fn clone(&self) -> Self {
+                Self::new($(self.$outer.clone()),+)
+            }
+        }
+    };
+}
+
+multi_pierce! {
+    /** Cache two doubly-nested pointers together.
+
+    Each slot caches its target independently, like a [`Pierce`][crate::Pierce] would.
+    ```
+    # use std::sync::Arc;
+    # use pierce::PiercePair;
+    let pair = PiercePair::new(Arc::new(vec![1, 2]), Box::new(String::from("edges")));
+    assert_eq!(pair.first(), &[1, 2]);
+    assert_eq!(pair.second(), "edges");
+    ```
+    */
+    PiercePair { A outer_a target_a first, B outer_b target_b second }
+}
+
+multi_pierce! {
+    /** Cache three doubly-nested pointers together.
+
+    See [`PiercePair`].
+    */
+    PierceTriple {
+        A outer_a target_a first,
+        B outer_b target_b second,
+        C outer_c target_c third
+    }
+}
+
+multi_pierce! {
+    /** Cache four doubly-nested pointers together.
+
+    See [`PiercePair`].
+    */
+    PierceQuad {
+        A outer_a target_a first,
+        B outer_b target_b second,
+        C outer_c target_c third,
+        D outer_d target_d fourth
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_pair_moved_into_vec() {
+        let nodes = Arc::new(vec![1u32, 2, 3]);
+        let pairs: Vec<PiercePair<Arc<Vec<u32>>, Box<String>>> = (0..16)
+            .map(|i| PiercePair::new(nodes.clone(), Box::new(i.to_string())))
+            .collect();
+        for (i, pair) in pairs.iter().enumerate() {
+            assert_eq!(pair.first() as *const [u32], &**nodes as *const [u32]);
+            assert_eq!(pair.second(), i.to_string());
+            let (first, second) = pair.as_refs();
+            assert_eq!(first, &[1, 2, 3]);
+            assert_eq!(second, i.to_string());
+        }
+        let (outer_a, outer_b) = pairs.into_iter().last().unwrap().into_outers();
+        assert!(Arc::ptr_eq(&outer_a, &nodes));
+        assert_eq!(*outer_b, "15");
+    }
+
+    #[test]
+    fn test_quad() {
+        let quad = PierceQuad::new(
+            Box::new(Box::new(1)),
+            Rc::new(String::from("two")),
+            Arc::new(vec![3]),
+            Box::new(Box::new(4.0)),
+        );
+        let cloned = quad.clone();
+        assert_eq!(cloned.as_refs(), (&1, "two", &[3][..], &4.0));
+        assert_eq!(quad.third() as *const [i32], cloned.third() as *const [i32]);
+        assert_eq!(*quad.fourth(), 4.0);
+    }
+}
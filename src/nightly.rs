@@ -0,0 +1,107 @@
+/* Everything that needs a nightly compiler, behind the `nightly` feature.
+The feature gates are enabled at the top of lib.rs:
+- `fn_traits`, `unboxed_closures` and `tuple_trait`: implementing the `Fn` traits, below.
+- `arbitrary_self_types` (tests only): `self: Pierce<..>` receivers. Pierce is already a `Receiver`
+  through core's blanket impl for `Deref` types, so nothing needs implementing for it.
+  Calling such methods through a trait object would need `DispatchFromDyn`,
+  which, like `CoerceUnsized`, can't be implemented for a struct with two pointer fields. */
+
+use core::ops::Deref;
+
+use crate::{Pierce, StableDeref};
+
+/* Calling a Pierce calls the target through the cached reference.
+Only shared access to the target is available, so all three traits need the target to be `Fn`. */
+impl<T, Args> FnOnce<Args> for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Fn<Args>,
+    Args: core::marker::Tuple,
+{
+    type Output = <<T::Target as Deref>::Target as FnOnce<Args>>::Output;
+    #[inline]
+    extern "rust-call" fn call_once(self, args: Args) -> Self::Output {
+        (*self).call(args)
+    }
+}
+
+impl<T, Args> FnMut<Args> for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Fn<Args>,
+    Args: core::marker::Tuple,
+{
+    #[inline]
+    extern "rust-call" fn call_mut(&mut self, args: Args) -> Self::Output {
+        (**self).call(args)
+    }
+}
+
+impl<T, Args> Fn<Args> for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Fn<Args>,
+    Args: core::marker::Tuple,
+{
+    #[inline]
+    extern "rust-call" fn call(&self, args: Args) -> Self::Output {
+        (**self).call(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    type Callback = Box<dyn Fn(u32) -> u32 + Send + Sync>;
+
+    #[test]
+    fn test_call() {
+        let handler: Pierce<Arc<Callback>> = Pierce::new(Arc::new(Box::new(|x| x + 1)));
+        assert_eq!(handler(1), 2);
+
+        fn apply<F: Fn(u32) -> u32>(f: F, x: u32) -> u32 {
+            f(x)
+        }
+        fn apply_twice<F: FnMut(u32) -> u32>(mut f: F) -> u32 {
+            f(1) + f(2)
+        }
+        fn apply_once<F: FnOnce(u32) -> u32>(f: F) -> u32 {
+            f(5)
+        }
+        assert_eq!(apply(&handler, 2), 3);
+        assert_eq!(apply_twice(&handler), 5);
+        assert_eq!(apply_once(handler), 6);
+    }
+
+    #[test]
+    fn test_self_receiver() {
+        // A Pierce<Box<Self>> would point at Self's own deref target, so the Self needs two boxes.
+        #[allow(clippy::redundant_allocation)]
+        trait Greet {
+            fn name(&self) -> &str;
+            fn greet(self: Pierce<Box<Box<Self>>>) -> String
+            where
+                Self: Sized,
+            {
+                format!("hello {}", self.name())
+            }
+        }
+        struct World;
+        impl Greet for World {
+            fn name(&self) -> &str {
+                "world"
+            }
+        }
+        let p = Pierce::new(Box::new(Box::new(World)));
+        assert_eq!(p.greet(), "hello world");
+
+        // Methods that don't take the Pierce by value still dispatch dynamically through it.
+        let dynamic: Pierce<Box<Box<dyn Greet>>> = Pierce::new(Box::new(Box::new(World)));
+        assert_eq!(dynamic.name(), "world");
+    }
+}
@@ -0,0 +1,245 @@
+use core::ops::{Add, BitAnd, BitOr, BitXor, Deref, Div, Index, Mul, Neg, Not, Rem, Shl, Shr, Sub};
+
+use crate::{Pierce, StableDeref};
+
+/* A Pierce only gives shared access to its target, so operators work on a copy of the target
+and return whatever the operator on the target returns, not a Pierce.
+Both `Pierce<T>` and `&Pierce<T>` get the impls, so `-&pierce` doesn't consume anything. */
+macro_rules! forward_unop {
+    ($($op_trait:ident $op_fn:ident),*) => {$(
+        impl<T> $op_trait for Pierce<T>
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            <T::Target as Deref>::Target: $op_trait + Copy,
+        {
+            type Output = <<T::Target as Deref>::Target as $op_trait>::Output;
+            #[inline]
+            fn $op_fn(self) -> Self::Output {
+                (*self).$op_fn()
+            }
+        }
+
+        impl<T> $op_trait for &Pierce<T>
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            <T::Target as Deref>::Target: $op_trait + Copy,
+        {
+            type Output = <<T::Target as Deref>::Target as $op_trait>::Output;
+            #[inline]
+            fn $op_fn(self) -> Self::Output {
+                (**self).$op_fn()
+            }
+        }
+    )*};
+}
+
+forward_unop!(Neg neg, Not not);
+
+macro_rules! forward_binop {
+    ($($op_trait:ident $op_fn:ident),*) => {$(
+        impl<T, R> $op_trait<R> for Pierce<T>
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            <T::Target as Deref>::Target: $op_trait<R> + Copy,
+        {
+            type Output = <<T::Target as Deref>::Target as $op_trait<R>>::Output;
+            #[inline]
+            fn $op_fn(self, rhs: R) -> Self::Output {
+                (*self).$op_fn(rhs)
+            }
+        }
+
+        impl<T, R> $op_trait<R> for &Pierce<T>
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            <T::Target as Deref>::Target: $op_trait<R> + Copy,
+        {
+            type Output = <<T::Target as Deref>::Target as $op_trait<R>>::Output;
+            #[inline]
+            fn $op_fn(self, rhs: R) -> Self::Output {
+                (**self).$op_fn(rhs)
+            }
+        }
+    )*};
+}
+
+forward_binop!(Add add, Sub sub, Mul mul, Div div, Rem rem);
+forward_binop!(BitAnd bitand, BitOr bitor, BitXor bitxor, Shl shl, Shr shr);
+
+/* `2.0 * pierce` for the commutative operators.
+The left hand side can't be generic because of the orphan rule, so the primitive numbers are listed out. */
+macro_rules! reverse_binop {
+    ($($lhs:ty),*) => {$(
+        reverse_binop!(@op $lhs, Add add);
+        reverse_binop!(@op $lhs, Mul mul);
+    )*};
+    (@op $lhs:ty, $op_trait:ident $op_fn:ident) => {
+        impl<T> $op_trait<Pierce<T>> for $lhs
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            <T::Target as Deref>::Target: Copy,
+            $lhs: $op_trait<<T::Target as Deref>::Target>,
+        {
+            type Output = <$lhs as $op_trait<<T::Target as Deref>::Target>>::Output;
+            #[inline]
+            fn $op_fn(self, rhs: Pierce<T>) -> Self::Output {
+                self.$op_fn(*rhs)
+            }
+        }
+
+        impl<T> $op_trait<&Pierce<T>> for $lhs
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+            <T::Target as Deref>::Target: Copy,
+            $lhs: $op_trait<<T::Target as Deref>::Target>,
+        {
+            type Output = <$lhs as $op_trait<<T::Target as Deref>::Target>>::Output;
+            #[inline]
+            fn $op_fn(self, rhs: &Pierce<T>) -> Self::Output {
+                self.$op_fn(**rhs)
+            }
+        }
+    };
+}
+
+reverse_binop!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/** Index the target, through the cache.
+
+Any index the target takes works, like `usize` and ranges for slices, or keys for maps.
+Out of bounds indexing panics just like indexing the target would.
+*/
+impl<T, I> Index<I> for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Index<I>,
+{
+    type Output = <<T::Target as Deref>::Target as Index<I>>::Output;
+    #[inline]
+    fn index(&self, index: I) -> &Self::Output {
+        &(**self)[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_neg() {
+        let p = Pierce::new(Box::new(Box::new(5i64)));
+        assert_eq!(-&p, -5);
+        let f = Pierce::new(Rc::new(Box::new(1.5f32)));
+        assert_eq!(-&f, -1.5);
+        assert_eq!(-p, -5i64);
+    }
+
+    #[test]
+    fn test_not() {
+        let b = Pierce::new(Box::new(Box::new(true)));
+        let not_b: bool = !&b;
+        assert!(!not_b);
+        let n = Pierce::new(Rc::new(Box::new(0b1010u8)));
+        assert_eq!(!&n, 0b1111_0101);
+        assert_eq!(!n, 0b1111_0101);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let p = Pierce::new(Box::new(Box::new(6.0f64)));
+        assert_eq!(&p + 1.5, 7.5);
+        assert_eq!(&p - 1.5, 4.5);
+        assert_eq!(&p * 2.0, 12.0);
+        assert_eq!(&p / 4.0, 1.5);
+        assert_eq!(&p % 4.0, 2.0);
+        assert_eq!(p + 1.0, 7.0);
+
+        let n = Pierce::new(Rc::new(Box::new(7u32)));
+        assert_eq!(&n / 2, 3);
+        assert_eq!(&n % 2, 1);
+    }
+
+    #[test]
+    fn test_arithmetic_reversed() {
+        let p = Pierce::new(Box::new(Box::new(6.0f64)));
+        assert_eq!(1.5 + &p, 7.5);
+        assert_eq!(2.0 * &p, 12.0);
+        let n = Pierce::new(Rc::new(Box::new(7i64)));
+        assert_eq!(3 * &n + 1, 22);
+        assert_eq!(3 + n, 10);
+    }
+
+    #[test]
+    fn test_bitwise() {
+        let p = Pierce::new(Box::new(Box::new(0b1100u64)));
+        assert_eq!(&p & 0b1010, 0b1000);
+        assert_eq!(&p | 0b1010, 0b1110);
+        assert_eq!(&p ^ 0b1010, 0b0110);
+        assert_eq!(&p << 2u32, 0b110000);
+        assert_eq!(&p >> 2u8, 0b11);
+        assert_eq!(p & u64::MAX, 0b1100);
+    }
+
+    #[test]
+    fn test_index() {
+        let v = Pierce::new(Rc::new(vec![10, 11, 12, 13, 14]));
+        assert_eq!(v[3], 13);
+        assert_eq!(v[1..4], [11, 12, 13]);
+        assert_eq!(v[..2], [10, 11]);
+        assert_eq!(v[3..], [13, 14]);
+        assert_eq!(v[..], [10, 11, 12, 13, 14]);
+        assert_eq!(v[1..=2], [11, 12]);
+
+        let s = Pierce::new(Box::new(String::from("hello world")));
+        assert_eq!(&s[6..], "world");
+        assert_eq!(&s[..5], "hello");
+    }
+
+    #[test]
+    fn test_index_ranges() {
+        let v = Pierce::new(Box::new(vec![0u8, 1, 2, 3, 4]));
+        let ranges: [&[u8]; 6] = [&v[1..3], &v[..3], &v[2..], &v[..], &v[1..=3], &v[..=2]];
+        let expected: [&[u8]; 6] = [
+            &[1, 2],
+            &[0, 1, 2],
+            &[2, 3, 4],
+            &[0, 1, 2, 3, 4],
+            &[1, 2, 3],
+            &[0, 1, 2],
+        ];
+        assert_eq!(ranges, expected);
+        // The slices point into the target, nothing is copied.
+        assert_eq!(v[2..].as_ptr(), v.as_ptr().wrapping_add(2));
+        assert!(v[5..].is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 6 out of range for slice of length 5")]
+    fn test_index_range_out_of_bounds() {
+        let v = Pierce::new(Box::new(vec![0u8; 5]));
+        let _ = &v[2..6];
+    }
+
+    #[test]
+    fn test_index_map() {
+        use std::collections::HashMap;
+        let map: HashMap<&str, i32> = HashMap::from([("a", 1), ("b", 2)]);
+        let p = Pierce::new(Box::new(Box::new(map)));
+        assert_eq!(p["b"], 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
+    fn test_index_out_of_bounds() {
+        let v = Pierce::new(Box::new(vec![1, 2, 3]));
+        let _ = v[3];
+    }
+}
@@ -0,0 +1,206 @@
+use core::ops::Deref;
+
+use crate::{Pierce, StableDeref};
+
+/** An optional [`Pierce`].
+
+An `OptionPierce<T>` stores either nothing or a `T` along with a cached pointer to `<T::Target as Deref>::Target`.
+It saves you from matching on `Option<Pierce<T>>` everywhere when your fields are optional nested pointers.
+
+The "nothing" state is stored in the niche of the cached pointer,
+so an `OptionPierce<T>` takes no more space than a `Pierce<T>`.
+```
+# use std::sync::Arc;
+# use pierce::OptionPierce;
+let mut op: OptionPierce<Arc<Vec<i32>>> = OptionPierce::new(None);
+assert!(op.as_deref().is_none());
+op.insert(Arc::new(vec![1, 2, 3]));
+assert_eq!(op.as_deref(), Some(&[1, 2, 3][..]));
+```
+*/
+pub struct OptionPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    inner: Option<Pierce<T>>,
+}
+
+impl<T> OptionPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    /** Create a new OptionPierce.
+
+    If `outer` is `Some`, it is double-deref-ed and the address of the target is cached, just like in [`Pierce::new`].
+     */
+    #[inline]
+    pub fn new(outer: Option<T>) -> Self {
+        Self {
+            inner: outer.map(Pierce::new),
+        }
+    }
+
+    /** Get the cached target, or `None` if there is no outer pointer.
+     */
+    #[inline]
+    pub fn as_deref(&self) -> Option<&<T::Target as Deref>::Target> {
+        self.inner.as_deref()
+    }
+
+    /** Returns `true` if there is an outer pointer.
+     */
+    #[inline]
+    pub fn is_some(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /** Returns `true` if there is no outer pointer.
+     */
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /** Store a new outer pointer, caching its target.
+
+    Any outer pointer previously stored is dropped.
+    Returns the newly cached target.
+     */
+    #[inline]
+    pub fn insert(&mut self, outer: T) -> &<T::Target as Deref>::Target {
+        self.inner.insert(Pierce::new(outer))
+    }
+
+    /** Take the outer pointer out, leaving `None` in its place.
+     */
+    #[inline]
+    pub fn take(&mut self) -> Option<T> {
+        self.inner.take().map(Pierce::into_outer)
+    }
+
+    /** Borrow the stored value as a [`Pierce`].
+     */
+    #[inline]
+    pub fn as_pierce(&self) -> Option<&Pierce<T>> {
+        self.inner.as_ref()
+    }
+
+    /** Borrow the outer pointer `T`.
+     */
+    #[inline]
+    pub fn borrow_outer(&self) -> Option<&T> {
+        self.inner.as_ref().map(Pierce::borrow_outer)
+    }
+
+    /** Get the stored value out as an `Option<Pierce<T>>`.
+     */
+    #[inline]
+    pub fn into_pierce(self) -> Option<Pierce<T>> {
+        self.inner
+    }
+
+    /** Get the outer pointer `T` out.
+     */
+    #[inline]
+    pub fn into_outer(self) -> Option<T> {
+        self.inner.map(Pierce::into_outer)
+    }
+}
+
+impl<T> Clone for OptionPierce<T>
+where
+    T: StableDeref + Clone,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Default for OptionPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn default() -> Self {
+        Self { inner: None }
+    }
+}
+
+impl<T> From<Option<T>> for OptionPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn from(outer: Option<T>) -> Self {
+        Self::new(outer)
+    }
+}
+
+impl<T> From<Option<Pierce<T>>> for OptionPierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn from(inner: Option<Pierce<T>>) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_none() {
+        let op: OptionPierce<Arc<Vec<i32>>> = OptionPierce::new(None);
+        assert!(op.is_none());
+        assert!(!op.is_some());
+        assert!(op.as_deref().is_none());
+        assert!(op.as_pierce().is_none());
+        assert!(op.into_outer().is_none());
+    }
+
+    #[test]
+    fn test_some_cached() {
+        let arc = Arc::new(vec![1, 2, 3]);
+        let target: *const [i32] = &**arc;
+        let op = OptionPierce::new(Some(arc));
+        assert!(op.is_some());
+        assert_eq!(op.as_deref().unwrap() as *const [i32], target);
+        assert_eq!(&**op.as_pierce().unwrap() as *const [i32], target);
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut op = OptionPierce::new(Some(Box::new(String::from("hello"))));
+        assert_eq!(op.as_deref(), Some("hello"));
+        assert_eq!(*op.take().unwrap(), "hello");
+        assert!(op.is_none());
+        assert_eq!(op.as_deref(), None);
+        assert_eq!(op.insert(Box::new(String::from("world"))), "world");
+        assert_eq!(op.as_deref(), Some("world"));
+    }
+
+    #[test]
+    fn test_size_of() {
+        use std::mem::size_of;
+        assert_eq!(
+            size_of::<OptionPierce<Arc<Vec<i32>>>>(),
+            size_of::<Option<Pierce<Arc<Vec<i32>>>>>()
+        );
+        assert_eq!(
+            size_of::<OptionPierce<Box<Box<i32>>>>(),
+            size_of::<Pierce<Box<Box<i32>>>>()
+        );
+    }
+}
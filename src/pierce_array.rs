@@ -0,0 +1,194 @@
+use core::ops::{Deref, Index};
+
+use crate::{Cached, StableDeref};
+
+/** A fixed-size batch of cached nested pointers.
+
+A `PierceArray<T, N>` stores `[T; N]` along with `N` cached pointers to `<T::Target as Deref>::Target`.
+Everything lives inline, so no heap allocation is made beyond what the pointers themselves own.
+
+```
+# use std::sync::Arc;
+# use pierce::PierceArray;
+let stages = PierceArray::new([
+    Arc::new(Box::new("vertex")),
+    Arc::new(Box::new("fragment")),
+]);
+assert_eq!(stages[1], "fragment");
+assert_eq!(stages.iter().count(), 2);
+```
+
+Like [`Pierce`][crate::Pierce], this only accepts [`StableDeref`] pointers,
+so every entry can be cached.
+*/
+pub struct PierceArray<T, const N: usize>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    outers: [T; N],
+    targets: [Cached<T>; N],
+}
+
+impl<T, const N: usize> PierceArray<T, N>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    /** Create a new PierceArray.
+
+    This method derefs each `T` twice and cache the address where each inner pointer points to,
+    just like [`Pierce::new`][crate::Pierce::new].
+     */
+    #[inline]
+    pub fn new(outers: [T; N]) -> Self {
+        let targets = core::array::from_fn(|idx| Cached::new(&outers[idx]));
+        Self { outers, targets }
+    }
+
+    /** Get the cached target at `index`, or `None` if out of bounds.
+     */
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&<T::Target as Deref>::Target> {
+        self.targets
+            .get(index)
+            .map(|target| unsafe { target.get() })
+        // SAFETY: Each cache was made from the outer pointer at the same index in `self.outers`.
+    }
+
+    /** Iterate over the cached targets.
+     */
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &<T::Target as Deref>::Target> + '_ {
+        self.targets.iter().map(|target| unsafe { target.get() })
+        // SAFETY: Each cache was made from the outer pointer at the same index in `self.outers`.
+    }
+
+    /** The number of entries. Always `N`.
+     */
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /** Returns `true` if `N` is zero.
+     */
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /** Borrow the outer pointers.
+     */
+    #[inline]
+    pub fn borrow_outers(&self) -> &[T; N] {
+        &self.outers
+    }
+
+    /** Get the outer pointers out.
+     */
+    #[inline]
+    pub fn into_outers(self) -> [T; N] {
+        self.outers
+    }
+}
+
+impl<T, const N: usize> Clone for PierceArray<T, N>
+where
+    T: StableDeref + Clone,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.outers.clone())
+    }
+}
+
+impl<T, const N: usize> Index<usize> for PierceArray<T, N>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    type Output = <T::Target as Deref>::Target;
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        unsafe { self.targets[index].get() }
+        // SAFETY: Each cache was made from the outer pointer at the same index in `self.outers`.
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for PierceArray<T, N>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn from(outers: [T; N]) -> Self {
+        Self::new(outers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::mem::size_of;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_empty() {
+        let pa: PierceArray<Box<Box<i32>>, 0> = PierceArray::new([]);
+        assert!(pa.is_empty());
+        assert_eq!(pa.iter().count(), 0);
+        assert_eq!(pa.get(0), None);
+        let _: [Box<Box<i32>>; 0] = pa.into_outers();
+    }
+
+    #[test]
+    fn test_one() {
+        let arc = Arc::new(vec![1, 2, 3]);
+        let pa = PierceArray::new([arc.clone()]);
+        assert_eq!(pa.len(), 1);
+        assert_eq!(&pa[0] as *const [i32], &**arc as *const [i32]);
+        let [outer] = pa.into_outers();
+        assert!(Arc::ptr_eq(&outer, &arc));
+    }
+
+    #[test]
+    fn test_many() {
+        let pa = PierceArray::new([0u64, 1, 2, 3, 4, 5, 6, 7].map(|i| Box::new(Box::new(i))));
+        assert_eq!(pa.len(), 8);
+        assert_eq!(pa[5], 5);
+        assert!(pa.iter().copied().eq(0..8));
+        let cloned = pa.clone();
+        assert!(cloned.iter().eq(pa.iter()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let pa = PierceArray::new([Box::new(Box::new(1))]);
+        let _ = pa[1];
+    }
+
+    #[test]
+    fn test_size_of() {
+        fn inner_test<T, const N: usize>()
+        where
+            T: StableDeref,
+            T::Target: StableDeref,
+        {
+            assert_eq!(
+                size_of::<PierceArray<T, N>>(),
+                N * (size_of::<T>() + size_of::<&<T::Target as Deref>::Target>())
+            );
+        }
+        inner_test::<Box<Box<i32>>, 0>();
+        inner_test::<Box<Box<i32>>, 1>();
+        inner_test::<Box<Box<i32>>, 8>();
+        inner_test::<Arc<Box<u8>>, 8>();
+        assert_eq!(
+            size_of::<PierceArray<Arc<Box<u8>>, 8>>(),
+            8 * (size_of::<Arc<Box<u8>>>() + size_of::<usize>())
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use core::ops::Deref;
+
+use crate::{Cached, Pierce, StableDeref};
+
+/** A [`Pierce`] with a guaranteed layout, for passing across FFI boundaries.
+
+`PierceC<T>` is `#[repr(C)]`: the outer pointer `T` comes first, followed by a `*const` pointer to the cached target.
+C code can read the target pointer directly, as long as it knows the size of `T`.
+Only `Sized` targets are supported, since pointers to unsized targets are fat and have no C equivalent.
+
+```
+# use pierce::{Pierce, PierceC};
+#[repr(C)]
+struct Header {
+    len: u32,
+}
+let pierce = PierceC::from(Pierce::new(Box::new(Box::new(Header { len: 3 }))));
+let ptr = pierce.as_ffi_ptr();
+// Pass `ptr` to C. The C side sees `struct { void *outer; const Header *target; }`.
+// SAFETY: `pierce` is still alive.
+let back = unsafe { PierceC::from_ffi_ptr(ptr) };
+assert_eq!(back.len, 3);
+```
+*/
+#[repr(C)]
+pub struct PierceC<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    outer: T,
+    target: Cached<T>,
+}
+
+impl<T> PierceC<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    /** Create a new PierceC.
+
+    This method derefs `T` twice and cache the address where the inner pointer points to,
+    just like [`Pierce::new`].
+     */
+    #[inline]
+    pub fn new(outer: T) -> Self {
+        Pierce::new(outer).into()
+    }
+
+    /** Get a pointer to this PierceC to hand to C.
+
+    The pointer is valid for as long as this PierceC is alive and not moved.
+     */
+    #[inline]
+    pub fn as_ffi_ptr(&self) -> *const Self {
+        self
+    }
+
+    /** Borrow a PierceC from a pointer given back by C.
+
+    # Safety
+    `ptr` must point to a live PierceC, like one returned by [`as_ffi_ptr`][Self::as_ffi_ptr],
+    and that PierceC must not be moved, dropped, or modified for `'a`.
+     */
+    #[inline]
+    pub unsafe fn from_ffi_ptr<'a>(ptr: *const Self) -> &'a Self {
+        &*ptr
+    }
+
+    /** Borrow the outer pointer `T`.
+     */
+    #[inline]
+    pub fn borrow_outer(&self) -> &T {
+        &self.outer
+    }
+
+    /** Get the outer pointer `T` out.
+     */
+    #[inline]
+    pub fn into_outer(self) -> T {
+        self.outer
+    }
+}
+
+impl<T> Deref for PierceC<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    type Target = <T::Target as Deref>::Target;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.target.get() }
+        // SAFETY: The cache was taken over from a Pierce along with its outer pointer, `self.outer`.
+    }
+}
+
+impl<T> From<Pierce<T>> for PierceC<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    #[inline]
+    fn from(pierce: Pierce<T>) -> Self {
+        Self {
+            target: pierce.target,
+            outer: pierce.outer,
+        }
+    }
+}
+
+impl<T> From<PierceC<T>> for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Sized,
+{
+    #[inline]
+    fn from(pierce: PierceC<T>) -> Self {
+        Self {
+            target: pierce.target,
+            outer: pierce.outer,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{mem::offset_of, sync::Arc};
+
+    #[test]
+    fn test_layout() {
+        type P = PierceC<Box<Box<u64>>>;
+        assert_eq!(offset_of!(P, outer), 0);
+        assert_eq!(offset_of!(P, target), size_of::<Box<Box<u64>>>());
+        assert_eq!(size_of::<P>(), 2 * size_of::<usize>());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let pierce = Pierce::new(Arc::new(Box::new(5u32)));
+        let target: *const u32 = &*pierce;
+        let c = PierceC::from(pierce);
+        assert_eq!(&*c as *const u32, target);
+        let ptr = c.as_ffi_ptr();
+        let borrowed = unsafe { PierceC::from_ffi_ptr(ptr) };
+        assert_eq!(**borrowed, 5);
+        assert_eq!(**borrowed.borrow_outer().as_ref(), 5);
+        let pierce: Pierce<_> = c.into();
+        assert_eq!(&*pierce as *const u32, target);
+        assert_eq!(*PierceC::new(pierce.into_outer()), 5);
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    borrow::Borrow,
+    collections::{hash_map, HashMap},
+    hash::{BuildHasher, Hash},
+    iter::FromIterator,
+    ops::Deref,
+};
+
+use crate::{Pierce, StableDeref};
+
+/** A [`HashMap`] whose values are cached nested pointers.
+
+`PierceMap<K, T>` stores each value `T` in a [`Pierce`],
+and lookups return the final target directly, so `get` costs a hash lookup plus a single jump.
+
+```
+# use std::sync::Arc;
+# use pierce::PierceMap;
+let mut samples: PierceMap<String, Arc<Vec<f64>>> = PierceMap::new();
+samples.insert(String::from("a"), Arc::new(vec![1.0, 2.0]));
+let a: &[f64] = samples.get("a").unwrap();
+assert_eq!(a, &[1.0, 2.0]);
+```
+*/
+pub struct PierceMap<K, T, S = hash_map::RandomState>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    map: HashMap<K, Pierce<T>, S>,
+}
+
+impl<K, T> PierceMap<K, T, hash_map::RandomState>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    /** Create a new, empty PierceMap.
+     */
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+        }
+    }
+}
+
+impl<K, T, S> PierceMap<K, T, S>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    /** Create a new, empty PierceMap that uses the given hasher.
+     */
+    #[inline]
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            map: HashMap::with_hasher(hasher),
+        }
+    }
+
+    /** The number of entries.
+     */
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /** Returns `true` if there are no entries.
+     */
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /** Iterate over the keys and the cached targets, in arbitrary order.
+     */
+    #[inline]
+    pub fn iter(&self) -> PierceMapIter<'_, K, T> {
+        PierceMapIter {
+            inner: self.map.iter(),
+        }
+    }
+
+    /** Iterate over the keys, in arbitrary order.
+     */
+    #[inline]
+    pub fn keys(&self) -> hash_map::Keys<'_, K, Pierce<T>> {
+        self.map.keys()
+    }
+
+    /** Iterate over the cached targets, in arbitrary order.
+     */
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &<T::Target as Deref>::Target> + '_ {
+        self.map.values().map(|pierce| &**pierce)
+    }
+
+    /** Borrow the underlying `HashMap`.
+     */
+    #[inline]
+    pub fn as_map(&self) -> &HashMap<K, Pierce<T>, S> {
+        &self.map
+    }
+
+    /** Get the underlying `HashMap` out.
+     */
+    #[inline]
+    pub fn into_map(self) -> HashMap<K, Pierce<T>, S> {
+        self.map
+    }
+}
+
+impl<K, T, S> PierceMap<K, T, S>
+where
+    K: Hash + Eq,
+    T: StableDeref,
+    T::Target: StableDeref,
+    S: BuildHasher,
+{
+    /** Insert an outer pointer, caching its target. Returns the outer pointer previously at `key`, if any.
+     */
+    #[inline]
+    pub fn insert(&mut self, key: K, outer: T) -> Option<T> {
+        self.map
+            .insert(key, Pierce::new(outer))
+            .map(Pierce::into_outer)
+    }
+
+    /** Get the target at `key`, inserting the outer pointer returned by `f` if there is none.
+
+    `f` is only called if `key` is absent.
+     */
+    #[inline]
+    pub fn insert_with<F>(&mut self, key: K, f: F) -> &<T::Target as Deref>::Target
+    where
+        F: FnOnce() -> T,
+    {
+        self.map.entry(key).or_insert_with(|| Pierce::new(f()))
+    }
+
+    /** Get the cached target at `key`.
+     */
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&<T::Target as Deref>::Target>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key).map(|pierce| &**pierce)
+    }
+
+    /** Get the Pierce at `key`, for when you need the outer pointer too.
+     */
+    #[inline]
+    pub fn get_pierce<Q>(&self, key: &Q) -> Option<&Pierce<T>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    /** Returns `true` if there is an entry at `key`.
+     */
+    #[inline]
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    /** Remove the entry at `key`, returning its outer pointer.
+     */
+    #[inline]
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<T>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.map.remove(key).map(Pierce::into_outer)
+    }
+}
+
+/** An iterator over the keys and cached targets of a [`PierceMap`].
+
+Created with [`PierceMap::iter`].
+*/
+pub struct PierceMapIter<'a, K, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    inner: hash_map::Iter<'a, K, Pierce<T>>,
+}
+
+impl<'a, K, T> Iterator for PierceMapIter<'a, K, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    type Item = (&'a K, &'a <T::Target as Deref>::Target);
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, pierce)| (key, &**pierce))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, T> ExactSizeIterator for PierceMapIter<'_, K, T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+}
+
+impl<'a, K, T, S> IntoIterator for &'a PierceMap<K, T, S>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    type Item = (&'a K, &'a <T::Target as Deref>::Target);
+    type IntoIter = PierceMapIter<'a, K, T>;
+    #[inline]
+    fn into_iter(self) -> PierceMapIter<'a, K, T> {
+        self.iter()
+    }
+}
+
+impl<K, T, S> Clone for PierceMap<K, T, S>
+where
+    K: Clone,
+    T: StableDeref + Clone,
+    T::Target: StableDeref,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<K, T, S> Default for PierceMap<K, T, S>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    S: Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, T, S> FromIterator<(K, T)> for PierceMap<K, T, S>
+where
+    K: Hash + Eq,
+    T: StableDeref,
+    T::Target: StableDeref,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, T)>>(iter: I) -> Self {
+        let mut map = Self::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, T, S> Extend<(K, T)> for PierceMap<K, T, S>
+where
+    K: Hash + Eq,
+    T: StableDeref,
+    T::Target: StableDeref,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, T)>>(&mut self, iter: I) {
+        self.map.extend(
+            iter.into_iter()
+                .map(|(key, outer)| (key, Pierce::new(outer))),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_get_is_cached_target() {
+        let arc = Arc::new(vec![1u32, 2, 3]);
+        let manual = Pierce::new(arc.clone());
+        let mut map = PierceMap::new();
+        assert_eq!(map.insert(String::from("a"), arc.clone()), None);
+        assert_eq!(
+            map.get("a").unwrap() as *const [u32],
+            &*manual as *const [u32]
+        );
+        assert!(Arc::ptr_eq(
+            map.get_pierce("a").unwrap().borrow_outer(),
+            &arc
+        ));
+        assert_eq!(map.get("b"), None);
+
+        let calls = std::cell::Cell::new(0);
+        let b = map.insert_with(String::from("b"), || {
+            calls.set(calls.get() + 1);
+            Arc::new(vec![4])
+        });
+        assert_eq!(b, &[4]);
+        map.insert_with(String::from("b"), || unreachable!());
+        assert_eq!(calls.get(), 1);
+
+        let mut pairs: Vec<(&String, &[u32])> = map.iter().collect();
+        pairs.sort();
+        assert_eq!(
+            pairs,
+            [
+                (&String::from("a"), &[1, 2, 3][..]),
+                (&String::from("b"), &[4][..])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remove_returns_outer() {
+        let arc = Arc::new(String::from("hello"));
+        let mut map: PierceMap<u8, Arc<String>> = vec![(1, arc.clone())].into_iter().collect();
+        let replaced = map.insert(1, Arc::new(String::from("world"))).unwrap();
+        assert!(Arc::ptr_eq(&replaced, &arc));
+        let removed = map.remove(&1).unwrap();
+        assert_eq!(*removed, "world");
+        assert!(map.is_empty());
+        assert_eq!(map.remove(&1), None);
+    }
+}
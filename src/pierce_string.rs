@@ -0,0 +1,75 @@
+use alloc::{boxed::Box, string::String};
+
+use crate::{shared_str::str_newtype, Pierce};
+
+/** An owned, immutable string, backed by a `Pierce<Box<String>>`.
+
+This is the owned counterpart of [`SharedStr`][crate::SharedStr]: cloning copies the string.
+Reading the string is a single jump, like with a plain `String`.
+Use it where a `Box<String>` is handed to you, or next to `SharedStr` where the string doesn't need sharing.
+
+`PierceString` compares, orders, and hashes exactly like `str`, and implements `Borrow<str>`,
+so it can be used as a map key and looked up by `&str`.
+
+```
+# use std::collections::HashMap;
+# use pierce::PierceString;
+let mut ages: HashMap<PierceString, u32> = HashMap::new();
+ages.insert(PierceString::from("alice"), 30);
+assert_eq!(ages.get("alice"), Some(&30));
+```
+*/
+// Pierce needs two levels of pointers, so the String is boxed.
+#[allow(clippy::box_collection)]
+#[derive(Clone, Default)]
+pub struct PierceString(Pierce<Box<String>>);
+
+str_newtype!(PierceString, Box);
+
+impl PierceString {
+    /** Get the string out. Nothing is copied.
+     */
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.0.into_inner()
+    }
+}
+
+impl From<Box<String>> for PierceString {
+    #[inline]
+    fn from(boxed: Box<String>) -> Self {
+        Self(Pierce::new(boxed))
+    }
+}
+
+impl From<PierceString> for String {
+    #[inline]
+    fn from(s: PierceString) -> Self {
+        s.into_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_copies() {
+        let a = PierceString::from("hello");
+        let b = a.clone();
+        assert_ne!(a.as_ptr(), b.as_ptr());
+        assert_eq!(a, b);
+        assert!(b.as_pierce().is_cache_valid());
+        drop(a);
+        assert_eq!(b, "hello");
+    }
+
+    #[test]
+    fn test_into_string() {
+        let s = PierceString::from(Box::new(String::from("moved")));
+        let ptr = s.as_ptr();
+        let string: String = s.into();
+        assert_eq!(string.as_ptr(), ptr);
+        assert_eq!(PierceString::default().into_string(), "");
+    }
+}
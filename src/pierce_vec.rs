@@ -0,0 +1,206 @@
+use alloc::vec::Vec;
+use core::{iter::FromIterator, ops::Deref};
+
+use crate::{Cached, StableDeref};
+
+/** A growable collection of cached nested pointers, stored as a structure of arrays.
+
+A `Vec<Pierce<T>>` interleaves the outer pointers with the cached target pointers in memory.
+A `PierceVec<T>` stores the outer pointers in one `Vec<T>` and the cached target pointers in another.
+Reading targets only touches the pointer array, so more cached pointers fit in each cache line.
+
+```
+# use pierce::PierceVec;
+let mut pv: PierceVec<Box<Box<i64>>> = PierceVec::new();
+pv.push(Box::new(Box::new(1)));
+pv.push(Box::new(Box::new(2)));
+assert_eq!(pv.get(1), Some(&2));
+assert_eq!(pv.iter().sum::<i64>(), 3);
+```
+
+Like [`Pierce`][crate::Pierce], this only accepts [`StableDeref`] pointers,
+so every entry can be cached and there is no slow path.
+*/
+pub struct PierceVec<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    outers: Vec<T>,
+    targets: Vec<Cached<T>>,
+}
+
+impl<T> PierceVec<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    /** Create a new, empty PierceVec.
+     */
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            outers: Vec::new(),
+            targets: Vec::new(),
+        }
+    }
+
+    /** Create a new, empty PierceVec with space for at least `capacity` entries.
+     */
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            outers: Vec::with_capacity(capacity),
+            targets: Vec::with_capacity(capacity),
+        }
+    }
+
+    /** Append an outer pointer, caching its target.
+
+    This method derefs `T` twice and cache the address where the inner pointer points to,
+    just like [`Pierce::new`][crate::Pierce::new].
+     */
+    #[inline]
+    pub fn push(&mut self, outer: T) {
+        let target = Cached::new(&outer);
+        self.outers.push(outer);
+        self.targets.push(target);
+    }
+
+    /** Get the cached target at `index`, or `None` if out of bounds.
+     */
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&<T::Target as Deref>::Target> {
+        self.targets
+            .get(index)
+            .map(|target| unsafe { target.get() })
+        // SAFETY: Each cache was made from the outer pointer at the same index in `self.outers`.
+    }
+
+    /** Iterate over the cached targets.
+
+    This only reads the array of cached pointers. The outer pointers are not touched.
+     */
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &<T::Target as Deref>::Target> + '_ {
+        self.targets.iter().map(|target| unsafe { target.get() })
+        // SAFETY: Each cache was made from the outer pointer at the same index in `self.outers`.
+    }
+
+    /** The number of entries.
+     */
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /** Returns `true` if there are no entries.
+     */
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /** Borrow the outer pointers.
+     */
+    #[inline]
+    pub fn borrow_outers(&self) -> &[T] {
+        &self.outers
+    }
+
+    /** Get the outer pointers out.
+     */
+    #[inline]
+    pub fn into_outers(self) -> Vec<T> {
+        self.outers
+    }
+}
+
+impl<T> Clone for PierceVec<T>
+where
+    T: StableDeref + Clone,
+    T::Target: StableDeref,
+{
+    fn clone(&self) -> Self {
+        self.outers.iter().cloned().collect()
+    }
+}
+
+impl<T> Default for PierceVec<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<Vec<T>> for PierceVec<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    fn from(outers: Vec<T>) -> Self {
+        let targets = outers.iter().map(Cached::new).collect();
+        Self { outers, targets }
+    }
+}
+
+impl<T> FromIterator<T> for PierceVec<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<T> Extend<T> for PierceVec<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for outer in iter {
+            self.push(outer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_push_get_iter() {
+        let mut pv = PierceVec::new();
+        assert!(pv.is_empty());
+        for i in 0..100 {
+            pv.push(Box::new(Box::new(i)));
+        }
+        assert_eq!(pv.len(), 100);
+        assert_eq!(pv.get(42), Some(&42));
+        assert_eq!(pv.get(100), None);
+        assert!(pv.iter().copied().eq(0..100));
+        let outers = pv.into_outers();
+        assert_eq!(**outers[99], 99);
+    }
+
+    #[test]
+    fn test_cached_address() {
+        let arcs: Vec<Arc<Vec<u8>>> = (0..4).map(|i| Arc::new(vec![i; 3])).collect();
+        let pv: PierceVec<_> = arcs.iter().cloned().collect();
+        for (i, arc) in arcs.iter().enumerate() {
+            assert_eq!(pv.get(i).unwrap() as *const [u8], &***arc as *const [u8]);
+        }
+        let cloned = pv.clone();
+        assert_eq!(
+            cloned.get(3).unwrap() as *const [u8],
+            pv.get(3).unwrap() as *const [u8]
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use core::ops::Deref;
+
+use crate::{Pierce, StableDeref};
+
+/** Doubly-nested pointers, cached in a [`Pierce`] or not.
+
+Generic code can take `&impl Pierced` to accept a `Pierce<Arc<Vec<u8>>>` as well as a plain `Arc<Vec<u8>>`,
+without making callers wrap their pointers.
+[`target`][Pierced::target] derefs twice for plain pointers, and reads the cache for a Pierce.
+
+It is implemented for `Pierce<T>`, and for `&U`, `Box<U>`, `Rc<U>` and `Arc<U>` when `U` derefs.
+A blanket implementation for everything that derefs twice isn't possible, since a Pierce derefs too.
+
+```
+# use std::sync::Arc;
+# use pierce::{Pierce, Pierced};
+fn checksum<P: Pierced + ?Sized>(src: &P) -> u32
+where
+    P::Target: AsRef<[u8]>,
+{
+    src.target().as_ref().iter().map(|&b| b as u32).sum()
+}
+let arc = Arc::new(vec![1u8, 2, 3]);
+assert_eq!(checksum(&arc), 6);
+assert_eq!(checksum(&Pierce::new(arc)), 6);
+```
+*/
+pub trait Pierced {
+    /** What the pointer derefs to, after two derefs.
+     */
+    type Target: ?Sized;
+
+    /** Get the target, deref-ing twice or reading the cache.
+     */
+    fn target(&self) -> &Self::Target;
+
+    /** Whether [`target`][Pierced::target] reads a cache instead of deref-ing. True only for a Pierce.
+     */
+    #[inline]
+    fn is_cached(&self) -> bool {
+        false
+    }
+}
+
+impl<T> Pierced for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    type Target = <T::Target as Deref>::Target;
+    #[inline]
+    fn target(&self) -> &Self::Target {
+        self
+    }
+    #[inline]
+    fn is_cached(&self) -> bool {
+        true
+    }
+}
+
+impl<U> Pierced for &U
+where
+    U: Deref + ?Sized,
+{
+    type Target = U::Target;
+    #[inline]
+    fn target(&self) -> &Self::Target {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+macro_rules! impl_pierced {
+    ($($ptr:ty),*) => {$(
+        impl<U> Pierced for $ptr
+        where
+            U: Deref + ?Sized,
+        {
+            type Target = U::Target;
+            #[inline]
+            fn target(&self) -> &Self::Target {
+                self
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "alloc")]
+impl_pierced!(alloc::boxed::Box<U>, alloc::rc::Rc<U>, alloc::sync::Arc<U>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn parse<P: Pierced + ?Sized>(src: &P) -> (Vec<u8>, bool)
+    where
+        P::Target: AsRef<[u8]>,
+    {
+        (src.target().as_ref().to_vec(), src.is_cached())
+    }
+
+    #[test]
+    #[allow(clippy::redundant_allocation)]
+    fn test_generic_over_pierced() {
+        let arc = Arc::new(vec![1u8, 2, 3, 4]);
+        assert_eq!(parse(&arc), (vec![1, 2, 3, 4], false));
+        let pierce = Pierce::new(arc.clone());
+        assert_eq!(parse(&pierce), (vec![1, 2, 3, 4], true));
+        let boxed: Box<Box<[u8; 4]>> = Box::new(Box::new([1, 2, 3, 4]));
+        assert_eq!(parse(&boxed), (vec![1, 2, 3, 4], false));
+        let vec = vec![1u8, 2, 3, 4];
+        assert_eq!(parse(&&vec), (vec![1, 2, 3, 4], false));
+    }
+
+    #[test]
+    fn test_same_target() {
+        let pierce = Pierce::new(Arc::new(String::from("hi")));
+        assert!(std::ptr::eq(pierce.target(), &***pierce.borrow_outer()));
+        assert!(std::ptr::eq(
+            pierce.borrow_outer().target(),
+            pierce.target()
+        ));
+    }
+}
@@ -0,0 +1,130 @@
+use core::{ops::Deref, pin::Pin};
+
+use crate::{Cached, StableDeref};
+
+/** A [`Pierce`][crate::Pierce] for nested pointers whose inner pointer is pinned.
+
+`PinnedPierce<T>` takes an outer pointer `T` that derefs to a `Pin<Q>`, like `Rc<Pin<Box<U>>>`,
+and caches the target of `Q`.
+Besides the normal `Deref`, [`as_pinned_target`][Self::as_pinned_target] returns the cached target as `Pin<&Target>`.
+
+```
+# use std::{marker::PhantomPinned, pin::Pin, rc::Rc};
+# use pierce::PinnedPierce;
+struct Inner {
+    value: i32,
+    _pin: PhantomPinned,
+}
+let outer: Rc<Pin<Box<Inner>>> = Rc::new(Box::pin(Inner { value: 5, _pin: PhantomPinned }));
+let pierce = PinnedPierce::new(outer);
+let pinned: Pin<&Inner> = pierce.as_pinned_target();
+assert_eq!(pinned.value, 5);
+```
+
+# Why this is sound
+
+The target is pinned by the `Pin<Q>` inside `T`, not by us:
+it can't be moved until it is dropped no matter what is done with `T`.
+`as_pinned_target` is just [`Pin::as_ref`] on that `Pin<Q>`, with the deref result taken from the cache.
+
+This is why the outer pointer can't simply be a `Pin<P>` with a `P::Target` that derefs to the target.
+Pinning `P::Target` says nothing about where *its* target lives.
+For example, with `Pin<Rc<Box<U>>>`, another clone of the `Rc` could be unwrapped later and the `U` moved out of the `Box`.
+*/
+pub struct PinnedPierce<T>
+where
+    T: StableDeref,
+    T::Target: Deref,
+{
+    outer: T,
+    target: Cached<T>,
+}
+
+impl<T, Q> PinnedPierce<T>
+where
+    T: StableDeref<Target = Pin<Q>>,
+    Q: StableDeref,
+{
+    /** Create a new PinnedPierce.
+
+    This method derefs `T` twice and cache the address where the inner pointer points to,
+    just like [`Pierce::new`][crate::Pierce::new].
+     */
+    #[inline]
+    pub fn new(outer: T) -> Self {
+        let target = Cached::new(&outer);
+        Self { outer, target }
+    }
+
+    /** Get the cached target, pinned.
+     */
+    #[inline]
+    pub fn as_pinned_target(&self) -> Pin<&Q::Target> {
+        unsafe { Pin::new_unchecked(&**self) }
+        // SAFETY: The target is pinned by the `Pin<Q>` in `self.outer`. See "Why this is sound" in the type docs.
+    }
+
+    /** Borrow the outer pointer `T`.
+     */
+    #[inline]
+    pub fn borrow_outer(&self) -> &T {
+        &self.outer
+    }
+
+    /** Get the outer pointer `T` out.
+     */
+    #[inline]
+    pub fn into_outer(self) -> T {
+        self.outer
+    }
+}
+
+impl<T, Q> Deref for PinnedPierce<T>
+where
+    T: StableDeref<Target = Pin<Q>>,
+    Q: StableDeref,
+{
+    type Target = Q::Target;
+    #[inline]
+    fn deref(&self) -> &Q::Target {
+        unsafe { self.target.get() }
+        // SAFETY: The cache was made from `self.outer`. `Pin<Q>` derefs through `Q`, which is StableDeref.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::marker::PhantomPinned;
+    use std::{rc::Rc, sync::Arc};
+
+    struct SelfAware {
+        value: u32,
+        _pin: PhantomPinned,
+    }
+
+    #[test]
+    fn test_pinned_target() {
+        let outer: Rc<Pin<Box<SelfAware>>> = Rc::new(Box::pin(SelfAware {
+            value: 7,
+            _pin: PhantomPinned,
+        }));
+        let expected: *const SelfAware = &**outer;
+        let pierce = PinnedPierce::new(outer.clone());
+        let pinned: Pin<&SelfAware> = pierce.as_pinned_target();
+        assert_eq!(&*pinned as *const SelfAware, expected);
+        assert_eq!(&*pierce as *const SelfAware, expected);
+        assert_eq!(pinned.value, 7);
+        assert!(Rc::ptr_eq(&pierce.into_outer(), &outer));
+    }
+
+    #[test]
+    fn test_send_across_threads() {
+        let pierce = PinnedPierce::new(Arc::new(Box::pin(String::from("hello"))));
+        let cloned = PinnedPierce::new(pierce.borrow_outer().clone());
+        std::thread::spawn(move || assert_eq!(&*cloned.as_pinned_target(), "hello"))
+            .join()
+            .unwrap();
+        assert_eq!(&*pierce, "hello");
+    }
+}
@@ -0,0 +1,59 @@
+use core::{fmt::Debug, ops::Deref};
+
+use proptest::{
+    arbitrary::Arbitrary,
+    strategy::{Map, Strategy},
+};
+
+use crate::{Pierce, StableDeref};
+
+impl<T> Arbitrary for Pierce<T>
+where
+    T: StableDeref + Arbitrary,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Debug,
+{
+    type Parameters = T::Parameters;
+    type Strategy = Map<T::Strategy, fn(T) -> Self>;
+
+    fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+        T::arbitrary_with(args).prop_map(Pierce::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        #[cfg_attr(miri, ignore = "too slow under Miri")]
+        fn test_cache_matches_outer(pierce in any::<Pierce<Box<Vec<u8>>>>()) {
+            prop_assert!(core::ptr::eq(&*pierce, &***pierce.borrow_outer()));
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore = "too slow under Miri")]
+        fn test_hash_matches_target(pierce in any::<Pierce<std::sync::Arc<String>>>()) {
+            use std::hash::BuildHasher;
+            let state = std::collections::hash_map::RandomState::new();
+            prop_assert_eq!(state.hash_one(&pierce), state.hash_one(&*pierce));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "too slow under Miri")]
+    fn test_shrinks_through_outer() {
+        use proptest::test_runner::{TestError, TestRunner};
+        let mut runner = TestRunner::default();
+        let result = runner.run(&any::<Pierce<Box<Vec<u8>>>>(), |pierce| {
+            prop_assert!(pierce.len() < 3);
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, minimal)) => assert_eq!(minimal.len(), 3),
+            _ => panic!("expected a failing case"),
+        }
+    }
+}
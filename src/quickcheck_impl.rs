@@ -0,0 +1,48 @@
+use alloc::boxed::Box;
+
+use quickcheck::{Arbitrary, Gen};
+
+use crate::{Pierce, StableDeref};
+
+impl<T> Arbitrary for Pierce<T>
+where
+    T: StableDeref + Arbitrary,
+    T::Target: StableDeref,
+{
+    fn arbitrary(g: &mut Gen) -> Self {
+        Self::new(T::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.borrow_outer().shrink().map(Self::new))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ops::Deref;
+    use quickcheck::quickcheck;
+    use std::sync::Arc;
+
+    #[test]
+    #[cfg_attr(miri, ignore = "too slow under Miri")]
+    fn test_cache_matches_outer() {
+        fn prop(pierce: Pierce<Arc<String>>) -> bool {
+            let uncached: &str = pierce.borrow_outer().deref().deref();
+            core::ptr::eq(&*pierce, uncached)
+        }
+        quickcheck(prop as fn(Pierce<Arc<String>>) -> bool);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore = "too slow under Miri")]
+    fn test_shrink() {
+        let pierce = Pierce::new(Box::new(vec![3u8, 1, 2]));
+        let shrunk: Vec<Pierce<Box<Vec<u8>>>> = pierce.shrink().collect();
+        assert!(shrunk.iter().any(|shrunk| shrunk.len() < 3));
+        assert!(shrunk
+            .iter()
+            .all(|shrunk| core::ptr::eq(&**shrunk, &***shrunk.borrow_outer())));
+    }
+}
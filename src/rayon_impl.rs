@@ -0,0 +1,41 @@
+use core::ops::Deref;
+
+use rayon::iter::IntoParallelIterator;
+
+use crate::{Pierce, StableDeref};
+
+/* Implementing `IntoParallelIterator` for `&Pierce<T>` gives us `par_iter()`
+through rayon's blanket `IntoParallelRefIterator` impl. */
+impl<'data, T> IntoParallelIterator for &'data Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    &'data <T::Target as Deref>::Target: IntoParallelIterator,
+{
+    type Iter = <&'data <T::Target as Deref>::Target as IntoParallelIterator>::Iter;
+    type Item = <&'data <T::Target as Deref>::Target as IntoParallelIterator>::Item;
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        (**self).into_par_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+    use std::sync::Arc;
+
+    #[test]
+    // rayon's thread pool does integer-to-pointer casts, which strict provenance rejects, and is very slow under Miri.
+    #[cfg_attr(miri, ignore)]
+    fn test_par_iter() {
+        let pierce = Pierce::new(Arc::new((0..1000u64).collect::<Vec<_>>()));
+        assert_eq!(pierce.par_iter().sum::<u64>(), 499500);
+        let doubled: Vec<u64> = (&pierce).into_par_iter().map(|x| x * 2).collect();
+        assert_eq!(doubled[999], 1998);
+
+        let boxed = Pierce::new(Box::new(Box::new([1, 2, 3])));
+        assert_eq!(boxed.par_iter().max(), Some(&3));
+    }
+}
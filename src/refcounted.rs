@@ -0,0 +1,81 @@
+use alloc::{rc::Rc, sync::Arc};
+
+use crate::{Pierce, StableDeref};
+
+// Reference count helpers for Pierces whose outer pointer is an `Rc` or an `Arc`.
+macro_rules! impl_counts {
+    ($($ptr:ident),*) => {$(
+        impl<U> Pierce<$ptr<U>>
+        where
+            U: StableDeref,
+        {
+            #[doc = concat!("The number of strong pointers to the allocation, like [`", stringify!($ptr), "::strong_count`].\n\nEvery Pierce made from a clone of the outer pointer counts.")]
+            #[inline]
+            pub fn strong_count(&self) -> usize {
+                $ptr::strong_count(self.borrow_outer())
+            }
+
+            #[doc = concat!("The number of weak pointers to the allocation, like [`", stringify!($ptr), "::weak_count`].")]
+            #[inline]
+            pub fn weak_count(&self) -> usize {
+                $ptr::weak_count(self.borrow_outer())
+            }
+
+            #[doc = concat!("Whether this is the only pointer to the allocation, strong or weak. This is when [`", stringify!($ptr), "::get_mut`] would succeed.")]
+            #[inline]
+            pub fn is_unique(&self) -> bool {
+                self.strong_count() == 1 && self.weak_count() == 0
+            }
+        }
+    )*};
+}
+
+impl_counts!(Rc, Arc);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        let arc = Arc::new(vec![1, 2, 3]);
+        let p = Pierce::new(arc.clone());
+        assert_eq!(p.strong_count(), 2);
+        let q = p.clone();
+        assert_eq!(q.strong_count(), 3);
+        let weak = Arc::downgrade(&arc);
+        assert_eq!(p.weak_count(), 1);
+        drop((arc, weak, q));
+        assert_eq!(p.strong_count(), 1);
+        assert_eq!(p.weak_count(), 0);
+
+        let rc = Pierce::new(Rc::new(String::from("hi")));
+        let _weak = Rc::downgrade(rc.borrow_outer());
+        assert_eq!(rc.strong_count(), 1);
+        assert_eq!(rc.weak_count(), 1);
+    }
+
+    #[test]
+    fn test_is_unique() {
+        let p = Pierce::new(Arc::new(vec![1]));
+        assert!(p.is_unique());
+        let q = p.clone();
+        assert!(!p.is_unique());
+        drop(q);
+        let weak = Arc::downgrade(p.borrow_outer());
+        assert!(!p.is_unique());
+        drop(weak);
+        let mut outer = p.into_outer();
+        assert!(Arc::get_mut(&mut outer).is_some());
+
+        let rc = Pierce::new(Rc::new(String::from("hi")));
+        assert!(rc.is_unique());
+        let weak = Rc::downgrade(rc.borrow_outer());
+        // Rc::get_mut also fails while there are weak pointers.
+        assert!(!rc.is_unique());
+        let mut outer = rc.into_outer();
+        assert!(Rc::get_mut(&mut outer).is_none());
+        drop(weak);
+        assert!(Rc::get_mut(&mut outer).is_some());
+    }
+}
@@ -0,0 +1,167 @@
+use core::ops::Deref;
+
+#[cfg(feature = "rkyv-validation")]
+use rkyv::CheckBytes;
+use rkyv::{Archive, Deserialize, Fallible, Serialize};
+
+use crate::{Pierce, StableDeref};
+
+/** The archived form of a [`Pierce<T>`].
+
+This is exactly the archived outer pointer `T::Archived`, which it derefs to.
+A `Pierce` only caches an address, and addresses don't survive archiving,
+so the cache is rebuilt when the `ArchivedPierce` is deserialized back into a `Pierce`.
+*/
+#[repr(transparent)]
+pub struct ArchivedPierce<T: Archive>(T::Archived);
+
+impl<T: Archive> ArchivedPierce<T> {
+    /** Get the archived outer pointer.
+     */
+    #[inline]
+    pub fn get(&self) -> &T::Archived {
+        &self.0
+    }
+
+    /** Deref the archived outer pointer twice, reaching the archived target.
+
+    For a `Pierce<Arc<Vec<u64>>>`, this is the `[u64]` inside the archive buffer.
+    Archived pointers are relative offsets, so there is nothing to cache: both derefs are additions.
+     */
+    #[inline]
+    pub fn target(&self) -> &<<T::Archived as Deref>::Target as Deref>::Target
+    where
+        T::Archived: Deref,
+        <T::Archived as Deref>::Target: Deref,
+    {
+        &self.0
+    }
+}
+
+/** Validate an archived Pierce by validating the archived outer pointer, with `check_archived_root`.
+ */
+#[cfg(feature = "rkyv-validation")]
+impl<T, C> CheckBytes<C> for ArchivedPierce<T>
+where
+    T: Archive,
+    T::Archived: CheckBytes<C>,
+    C: ?Sized,
+{
+    type Error = <T::Archived as CheckBytes<C>>::Error;
+    #[inline]
+    unsafe fn check_bytes<'a>(
+        value: *const Self,
+        context: &mut C,
+    ) -> Result<&'a Self, Self::Error> {
+        T::Archived::check_bytes(value.cast(), context)?;
+        Ok(&*value)
+        // SAFETY: ArchivedPierce<T> is a repr(transparent) wrapper of T::Archived, which was just checked.
+    }
+}
+
+impl<T: Archive> Deref for ArchivedPierce<T> {
+    type Target = T::Archived;
+    #[inline]
+    fn deref(&self) -> &T::Archived {
+        &self.0
+    }
+}
+
+impl<T> Archive for Pierce<T>
+where
+    T: StableDeref + Archive,
+    T::Target: StableDeref,
+{
+    type Archived = ArchivedPierce<T>;
+    type Resolver = T::Resolver;
+    #[inline]
+    unsafe fn resolve(&self, pos: usize, resolver: T::Resolver, out: *mut ArchivedPierce<T>) {
+        self.borrow_outer().resolve(pos, resolver, out.cast());
+        // SAFETY: ArchivedPierce<T> is a repr(transparent) wrapper of T::Archived.
+    }
+}
+
+impl<T, S> Serialize<S> for Pierce<T>
+where
+    T: StableDeref + Serialize<S>,
+    T::Target: StableDeref,
+    S: Fallible + ?Sized,
+{
+    #[inline]
+    fn serialize(&self, serializer: &mut S) -> Result<T::Resolver, S::Error> {
+        self.borrow_outer().serialize(serializer)
+    }
+}
+
+impl<T, D> Deserialize<Pierce<T>, D> for ArchivedPierce<T>
+where
+    T: StableDeref + Archive,
+    T::Target: StableDeref,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize(&self, deserializer: &mut D) -> Result<Pierce<T>, D::Error> {
+        Ok(Pierce::new(self.0.deserialize(deserializer)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rkyv::{archived_root, ser::serializers::AllocSerializer, ser::Serializer, Infallible};
+    #[test]
+    // rkyv 0.7's relative pointers are rejected by Stacked Borrows (but pass with -Zmiri-tree-borrows).
+    #[cfg_attr(miri, ignore)]
+    fn test_roundtrip() {
+        let pierce = Pierce::new(Box::new(vec![1u32, 2, 3]));
+        let mut serializer = AllocSerializer::<256>::default();
+        serializer.serialize_value(&pierce).unwrap();
+        let bytes = serializer.into_serializer().into_inner();
+
+        let archived = unsafe { archived_root::<Pierce<Box<Vec<u32>>>>(&bytes) };
+        assert_eq!(archived.get().as_slice(), &[1, 2, 3]);
+
+        let deserialized: Pierce<Box<Vec<u32>>> = archived.deserialize(&mut Infallible).unwrap();
+        assert_eq!(&*deserialized, &[1, 2, 3]);
+        assert_eq!(
+            &*deserialized as *const [u32],
+            &***deserialized.borrow_outer() as *const [u32]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rkyv-validation")]
+    #[cfg_attr(miri, ignore)]
+    fn test_validated_arc() {
+        use rkyv::check_archived_root;
+        use std::sync::Arc;
+
+        let table = Pierce::new(Arc::new((0..100u64).map(|x| x * x).collect::<Vec<_>>()));
+        let bytes = rkyv::to_bytes::<_, 1024>(&table).unwrap();
+
+        let archived = check_archived_root::<Pierce<Arc<Vec<u64>>>>(&bytes).unwrap();
+        assert_eq!(archived.target().len(), 100);
+        assert_eq!(archived.target()[9], 81);
+        // The target is read straight out of the buffer.
+        let buffer = bytes.as_ptr_range();
+        assert!(buffer.contains(&(archived.target().as_ptr() as *const u8)));
+
+        let deserialized: Pierce<Arc<Vec<u64>>> = archived
+            .deserialize(&mut rkyv::de::deserializers::SharedDeserializeMap::new())
+            .unwrap();
+        assert_eq!(deserialized, table);
+        assert!(core::ptr::eq(
+            &*deserialized,
+            &***deserialized.borrow_outer()
+        ));
+
+        // The archived Arc is the root, at the end of the buffer. Corrupting its pointer fails validation.
+        let mut corrupted = bytes.to_vec();
+        let len = corrupted.len();
+        corrupted[len - 4..].copy_from_slice(&u32::MAX.to_le_bytes());
+        let mut aligned = rkyv::AlignedVec::new();
+        aligned.extend_from_slice(&corrupted);
+        assert!(check_archived_root::<Pierce<Arc<Vec<u64>>>>(&aligned).is_err());
+    }
+}
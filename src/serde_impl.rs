@@ -0,0 +1,184 @@
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+use core::ops::Deref;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Pierce, StableDeref};
+
+/** Outer pointers that can be constructed from their deref target.
+
+This is used to deserialize a `Pierce<T>`:
+the inner pointer `T::Target` is deserialized, wrapped up into `T`, then cached.
+*/
+pub trait PierceConstruct: StableDeref
+where
+    Self::Target: StableDeref + Sized,
+{
+    /** Wrap the inner pointer up into the outer pointer.
+     */
+    fn construct(inner: Self::Target) -> Self;
+}
+
+impl<U: StableDeref> PierceConstruct for Box<U> {
+    #[inline]
+    fn construct(inner: U) -> Self {
+        Box::new(inner)
+    }
+}
+
+impl<U: StableDeref> PierceConstruct for Rc<U> {
+    #[inline]
+    fn construct(inner: U) -> Self {
+        Rc::new(inner)
+    }
+}
+
+impl<U: StableDeref> PierceConstruct for Arc<U> {
+    #[inline]
+    fn construct(inner: U) -> Self {
+        Arc::new(inner)
+    }
+}
+
+/** Serialize a Pierce as its target.
+
+The outer and inner pointers are an implementation detail,
+so a `Pierce<Box<Vec<i32>>>` serializes exactly like `[i32]` would.
+*/
+impl<T> Serialize for Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+    <T::Target as Deref>::Target: Serialize,
+{
+    #[inline]
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Serialize::serialize(&**self, serializer)
+    }
+}
+
+/** Deserialize a Pierce from its inner pointer.
+
+This is the counterpart of the `Serialize` impl:
+the data is read as `T::Target`, wrapped up using [`PierceConstruct`], and a fresh Pierce is created.
+*/
+impl<'de, T> Deserialize<'de> for Pierce<T>
+where
+    T: PierceConstruct,
+    T::Target: StableDeref + Sized + Deserialize<'de>,
+{
+    #[inline]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::Target::deserialize(deserializer).map(|inner| Pierce::new(T::construct(inner)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_transparent() {
+        let pierce = Pierce::new(Box::new(vec![1, 2, 3]));
+        assert_eq!(
+            serde_json::to_string(&pierce).unwrap(),
+            serde_json::to_string(&[1, 2, 3]).unwrap()
+        );
+        let pierce = Pierce::new(Arc::new(String::from("hello")));
+        assert_eq!(serde_json::to_string(&pierce).unwrap(), "\"hello\"");
+    }
+
+    #[test]
+    #[allow(clippy::redundant_allocation)]
+    fn test_serialize_same_bytes_as_target() {
+        use std::collections::BTreeMap;
+
+        #[derive(Serialize)]
+        struct Item {
+            id: u32,
+            tags: Vec<&'static str>,
+        }
+        let items = vec![
+            Item {
+                id: 1,
+                tags: vec!["a"],
+            },
+            Item {
+                id: 2,
+                tags: vec![],
+            },
+        ];
+        let plain = serde_json::to_vec(&items).unwrap();
+        assert_eq!(
+            serde_json::to_vec(&Pierce::new(Arc::new(items))).unwrap(),
+            plain
+        );
+
+        let item = Item {
+            id: 3,
+            tags: vec!["b", "c"],
+        };
+        let plain = serde_json::to_vec(&item).unwrap();
+        assert_eq!(
+            serde_json::to_vec(&Pierce::new(Box::new(Box::new(item)))).unwrap(),
+            plain
+        );
+
+        #[derive(Serialize)]
+        struct Response {
+            items: Pierce<Arc<Vec<u32>>>,
+            name: Pierce<Rc<String>>,
+            #[serde(flatten)]
+            extra: Pierce<Arc<Box<BTreeMap<String, u32>>>>,
+        }
+        let response = Response {
+            items: Pierce::new(Arc::new(vec![1, 2])),
+            name: Pierce::new(Rc::new(String::from("r"))),
+            extra: Pierce::new(Arc::new(Box::new(BTreeMap::from([(String::from("x"), 9)])))),
+        };
+        assert_eq!(
+            serde_json::to_string(&response).unwrap(),
+            r#"{"items":[1,2],"name":"r","x":9}"#
+        );
+    }
+
+    #[test]
+    fn test_deserialize_round_trip() {
+        use crate::Pierced;
+
+        let pierce: Pierce<Box<Vec<u32>>> = serde_json::from_str("[4,5,6]").unwrap();
+        assert!(pierce.is_cached());
+        assert!(core::ptr::eq(&*pierce, &***pierce.borrow_outer()));
+        assert_eq!(serde_json::to_string(&pierce).unwrap(), "[4,5,6]");
+
+        let pierce: Pierce<Arc<String>> = serde_json::from_str("\"hi\"").unwrap();
+        assert!(pierce.is_cached());
+        assert_eq!(serde_json::to_string(&pierce).unwrap(), "\"hi\"");
+
+        #[derive(Serialize, Deserialize)]
+        struct Response {
+            items: Pierce<Arc<Vec<u32>>>,
+            name: Pierce<Rc<String>>,
+        }
+        let json = r#"{"items":[1,2],"name":"r"}"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+        assert_eq!(response.items, [1, 2]);
+        assert_eq!(response.name, "r");
+        assert_eq!(serde_json::to_string(&response).unwrap(), json);
+        assert!(serde_json::from_str::<Response>(r#"{"items":"no","name":"r"}"#).is_err());
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let pierce: Pierce<Box<Vec<i32>>> = serde_json::from_str("[1,2,3]").unwrap();
+        assert_eq!(&*pierce, &[1, 2, 3]);
+        let pierce: Pierce<Rc<String>> = serde_json::from_str("\"hello\"").unwrap();
+        assert_eq!(&*pierce, "hello");
+        let pierce: Pierce<Arc<Vec<String>>> = serde_json::from_str("[\"a\",\"b\"]").unwrap();
+        assert_eq!(pierce.len(), 2);
+        assert_eq!(
+            serde_json::to_string(&pierce).unwrap(),
+            serde_json::to_string(&["a", "b"]).unwrap()
+        );
+    }
+}
@@ -0,0 +1,269 @@
+/*! Serialize shared Pierces once, and share them again when deserializing.
+
+Plain serde writes out an `Arc` every time it is reached, and reads every copy into its own allocation.
+With `#[serde(with = "pierce::serde_shared")]` on `Pierce<Arc<_>>` or `Pierce<Rc<_>>` fields,
+the first field pointing at an allocation writes the data along with an id, and the others only write the id.
+Deserializing gives back clones of one `Arc`.
+
+The ids only mean something within one call to [`scope`], so serializing and deserializing must each run inside one.
+Using the module outside of a scope is an error.
+
+```
+# use std::sync::Arc;
+# use pierce::{serde_shared, Pierce};
+# use serde::{Deserialize, Serialize};
+#[derive(Serialize, Deserialize)]
+struct Table {
+    #[serde(with = "serde_shared")]
+    xs: Pierce<Arc<Vec<f64>>>,
+    #[serde(with = "serde_shared")]
+    ys: Pierce<Arc<Vec<f64>>>,
+}
+let data = Pierce::new(Arc::new(vec![0.5; 1000]));
+let table = Table { xs: data.clone(), ys: data };
+let json = serde_shared::scope(|| serde_json::to_string(&table)).unwrap();
+assert!(json.len() < 5000);
+let back: Table = serde_shared::scope(|| serde_json::from_str(&json)).unwrap();
+assert!(Arc::ptr_eq(back.xs.borrow_outer(), back.ys.borrow_outer()));
+```
+
+Each Pierce is written as an `(id, Option<data>)` tuple, so this works with formats that aren't self-describing too.
+*/
+
+use std::{any::Any, cell::RefCell, collections::HashMap, fmt};
+
+use serde::{
+    de::{self, Deserialize, Deserializer, SeqAccess, Visitor},
+    ser::{self, Serialize, SerializeTuple, Serializer},
+};
+
+use crate::{CloneStableDeref, Pierce, PierceConstruct, StableDeref};
+
+const NO_SCOPE: &str = "pierce::serde_shared can only be used inside pierce::serde_shared::scope";
+
+#[derive(Default)]
+struct Tables {
+    // The address of each inner pointer written so far, its id,
+    // and a clone of its outer pointer, so the address can't be reused while the scope lives.
+    written: HashMap<*const (), (u64, Box<dyn Any>)>,
+    // A clone of each outer pointer read so far, by id.
+    read: HashMap<u64, Box<dyn Any>>,
+}
+
+thread_local! {
+    static TABLES: RefCell<Option<Tables>> = const { RefCell::new(None) };
+}
+
+/** Run `f`, with `#[serde(with = "pierce::serde_shared")]` fields sharing data within it.
+
+Pierces pointing at the same allocation are only written once per scope,
+and ids read within a scope resolve to the same allocation.
+Every pointer written or read is kept alive until the scope ends, so one allocation's address is never mistaken for another's.
+Scopes can be nested; the inner scope starts fresh and the outer one is restored after it.
+*/
+pub fn scope<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Tables>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let outer = self.0.take();
+            TABLES.with(|tables| *tables.borrow_mut() = outer);
+        }
+    }
+    let _restore = Restore(TABLES.with(|tables| tables.replace(Some(Tables::default()))));
+    f()
+}
+
+/** Serialize a shared Pierce, writing its data only the first time it's seen in the current [`scope`].
+ */
+pub fn serialize<T, S>(pierce: &Pierce<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: CloneStableDeref + 'static,
+    T::Target: StableDeref + Serialize,
+    S: Serializer,
+{
+    let inner: &T::Target = pierce.borrow_outer();
+    let address = (inner as *const T::Target).cast::<()>();
+    let seen = TABLES.with(|tables| {
+        let mut tables = tables.borrow_mut();
+        let tables = tables.as_mut()?;
+        let next = tables.written.len() as u64;
+        Some(match tables.written.get(&address) {
+            Some(&(id, _)) => (id, false),
+            None => {
+                let outer = pierce.borrow_outer().clone();
+                tables.written.insert(address, (next, Box::new(outer)));
+                (next, true)
+            }
+        })
+    });
+    let (id, first) = seen.ok_or_else(|| ser::Error::custom(NO_SCOPE))?;
+    let mut tuple = serializer.serialize_tuple(2)?;
+    tuple.serialize_element(&id)?;
+    tuple.serialize_element(&if first { Some(inner) } else { None })?;
+    tuple.end()
+}
+
+/** Deserialize a shared Pierce, reusing the outer pointer read earlier in the current [`scope`] under the same id.
+ */
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Pierce<T>, D::Error>
+where
+    T: PierceConstruct + CloneStableDeref + 'static,
+    T::Target: StableDeref + Sized + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    let (id, inner) =
+        deserializer.deserialize_tuple(2, EntryVisitor::<T::Target>(Default::default()))?;
+    let outer = TABLES.with(|tables| {
+        let mut tables = tables.borrow_mut();
+        let tables = tables.as_mut().ok_or_else(|| de::Error::custom(NO_SCOPE))?;
+        match inner {
+            Some(inner) => {
+                let outer = T::construct(inner);
+                tables.read.insert(id, Box::new(outer.clone()));
+                Ok(outer)
+            }
+            None => tables
+                .read
+                .get(&id)
+                .and_then(|outer| outer.downcast_ref::<T>())
+                .cloned()
+                .ok_or_else(|| {
+                    de::Error::custom(format_args!(
+                        "shared pointer {} is used before it is defined",
+                        id
+                    ))
+                }),
+        }
+    })?;
+    Ok(Pierce::new(outer))
+}
+
+struct EntryVisitor<U>(core::marker::PhantomData<U>);
+
+impl<'de, U: Deserialize<'de>> Visitor<'de> for EntryVisitor<U> {
+    type Value = (u64, Option<U>);
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a shared pointer id, followed by its data or nothing")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let id = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let inner = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Ok((id, inner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[derive(Serialize, Deserialize)]
+    struct Shared {
+        #[serde(with = "super")]
+        a: Pierce<Arc<Vec<f64>>>,
+        #[serde(with = "super")]
+        b: Pierce<Arc<Vec<f64>>>,
+        #[serde(with = "super")]
+        c: Pierce<Arc<Vec<f64>>>,
+    }
+
+    #[test]
+    fn test_round_trip_keeps_sharing() {
+        let data = Pierce::new(Arc::new(vec![1.5, 2.5]));
+        let other = Pierce::new(Arc::new(vec![1.5, 2.5]));
+        let value = Shared {
+            a: data.clone(),
+            b: other,
+            c: data,
+        };
+        let json = scope(|| serde_json::to_string(&value)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"a":[0,[1.5,2.5]],"b":[1,[1.5,2.5]],"c":[0,null]}"#
+        );
+
+        let back: Shared = scope(|| serde_json::from_str(&json)).unwrap();
+        assert!(Arc::ptr_eq(back.a.borrow_outer(), back.c.borrow_outer()));
+        assert!(!Arc::ptr_eq(back.a.borrow_outer(), back.b.borrow_outer()));
+        assert_eq!(back.b, [1.5, 2.5]);
+        assert!(std::ptr::eq(&*back.a, &*back.c));
+        assert_eq!(Arc::strong_count(back.a.borrow_outer()), 2);
+    }
+
+    #[test]
+    fn test_marked_shared_input() {
+        let json = r#"{"a":[7,[1.0]],"b":[7,null],"c":[8,[]]}"#;
+        let back: Shared = scope(|| serde_json::from_str(json)).unwrap();
+        assert!(Arc::ptr_eq(back.a.borrow_outer(), back.b.borrow_outer()));
+        assert!(back.c.is_empty());
+
+        let err =
+            scope(|| serde_json::from_str::<Shared>(r#"{"a":[1,null],"b":[1,[]],"c":[2,[]]}"#))
+                .map(drop)
+                .unwrap_err();
+        assert!(err.to_string().contains("used before it is defined"));
+    }
+
+    #[test]
+    fn test_rc_and_nested_scopes() {
+        #[derive(Serialize, Deserialize)]
+        struct Names(
+            #[serde(with = "super")] Pierce<Rc<String>>,
+            #[serde(with = "super")] Pierce<Rc<String>>,
+        );
+        let name = Pierce::new(Rc::new(String::from("x")));
+        let names = Names(name.clone(), name);
+        let json = scope(|| {
+            // An inner scope doesn't see the outer one's ids.
+            let inner = scope(|| serde_json::to_string(&names)).unwrap();
+            assert_eq!(inner, r#"[[0,"x"],[0,null]]"#);
+            serde_json::to_string(&names)
+        })
+        .unwrap();
+        assert_eq!(json, r#"[[0,"x"],[0,null]]"#);
+        let back: Names = scope(|| serde_json::from_str(&json)).unwrap();
+        assert!(Rc::ptr_eq(back.0.borrow_outer(), back.1.borrow_outer()));
+    }
+
+    #[test]
+    fn test_dropped_values_in_one_scope() {
+        // Each value is dropped before the next is made, so without the scope keeping them alive
+        // the allocator could hand out the same address again.
+        let json: Vec<String> = scope(|| {
+            (0..3u64)
+                .map(|i| {
+                    let value = Pierce::new(Arc::new(vec![i]));
+                    serde_json::to_string(&Wrapped(value)).unwrap()
+                })
+                .collect()
+        });
+        assert_eq!(json, ["[0,[0]]", "[1,[1]]", "[2,[2]]"]);
+    }
+
+    #[derive(Serialize)]
+    struct Wrapped(#[serde(with = "super")] Pierce<Arc<Vec<u64>>>);
+
+    #[test]
+    fn test_outside_scope() {
+        let value = Shared {
+            a: Pierce::new(Arc::new(vec![])),
+            b: Pierce::new(Arc::new(vec![])),
+            c: Pierce::new(Arc::new(vec![])),
+        };
+        let err = serde_json::to_string(&value).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("inside pierce::serde_shared::scope"));
+        assert!(
+            serde_json::from_str::<Shared>(r#"{"a":[0,[]],"b":[0,null],"c":[0,null]}"#).is_err()
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use alloc::{string::String, sync::Arc};
+
+use crate::Pierce;
+
+/* Everything SharedStr and PierceString have in common: both are a `str` behind a Pierce,
+so they read, compare, order, hash, print and serialize exactly like that `str`.
+Only the outer pointer differs, and with it what cloning does; each type writes those parts itself. */
+macro_rules! str_newtype {
+    ($name:ident, $ptr:ident) => {
+        impl $name {
+            /** Borrow the string.
+             */
+            #[inline]
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /** Borrow the underlying Pierce.
+             */
+            #[inline]
+            pub fn as_pierce(&self) -> &$crate::Pierce<$ptr<::alloc::string::String>> {
+                &self.0
+            }
+
+            /** Get the underlying Pierce out.
+             */
+            #[inline]
+            pub fn into_pierce(self) -> $crate::Pierce<$ptr<::alloc::string::String>> {
+                self.0
+            }
+        }
+
+        impl ::core::ops::Deref for $name {
+            type Target = str;
+            #[inline]
+            fn deref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl ::core::convert::AsRef<str> for $name {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self
+            }
+        }
+
+        impl ::core::borrow::Borrow<str> for $name {
+            #[inline]
+            fn borrow(&self) -> &str {
+                self
+            }
+        }
+
+        impl ::core::convert::From<::alloc::string::String> for $name {
+            #[inline]
+            fn from(s: ::alloc::string::String) -> Self {
+                Self($crate::Pierce::new($ptr::new(s)))
+            }
+        }
+
+        impl ::core::convert::From<&str> for $name {
+            #[inline]
+            fn from(s: &str) -> Self {
+                Self::from(::alloc::string::String::from(s))
+            }
+        }
+
+        impl ::core::convert::From<$crate::Pierce<$ptr<::alloc::string::String>>> for $name {
+            #[inline]
+            fn from(pierce: $crate::Pierce<$ptr<::alloc::string::String>>) -> Self {
+                Self(pierce)
+            }
+        }
+
+        impl ::core::cmp::PartialEq for $name {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.as_str() == other.as_str()
+            }
+        }
+
+        impl ::core::cmp::Eq for $name {}
+
+        impl ::core::cmp::PartialEq<str> for $name {
+            #[inline]
+            fn eq(&self, other: &str) -> bool {
+                self.as_str() == other
+            }
+        }
+
+        impl ::core::cmp::PartialEq<&str> for $name {
+            #[inline]
+            fn eq(&self, other: &&str) -> bool {
+                self.as_str() == *other
+            }
+        }
+
+        impl ::core::cmp::PartialEq<::alloc::string::String> for $name {
+            #[inline]
+            fn eq(&self, other: &::alloc::string::String) -> bool {
+                self.as_str() == other.as_str()
+            }
+        }
+
+        impl ::core::cmp::PartialOrd for $name {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl ::core::cmp::Ord for $name {
+            #[inline]
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                self.as_str().cmp(other.as_str())
+            }
+        }
+
+        // Must hash like `str` for the `Borrow<str>` impl to be correct.
+        impl ::core::hash::Hash for $name {
+            #[inline]
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                self.as_str().hash(state)
+            }
+        }
+
+        impl ::core::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Display::fmt(self.as_str(), f)
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                ::core::fmt::Debug::fmt(self.as_str(), f)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl serde::Serialize for $name {
+            #[inline]
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self)
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        impl<'de> serde::Deserialize<'de> for $name {
+            #[inline]
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                <::alloc::string::String as serde::Deserialize>::deserialize(deserializer)
+                    .map(Self::from)
+            }
+        }
+    };
+}
+
+pub(crate) use str_newtype;
+
+/** A cheaply clonable, immutable string, backed by a `Pierce<Arc<String>>`.
+
+Cloning bumps the reference count and copies the cached pointer.
+Reading the string is a single jump, not two.
+
+`SharedStr` compares, orders, and hashes exactly like `str`, and implements `Borrow<str>`,
+so it can be used as a map key and looked up by `&str`.
+
+```
+# use std::collections::HashMap;
+# use pierce::SharedStr;
+let mut ages: HashMap<SharedStr, u32> = HashMap::new();
+ages.insert(SharedStr::from("alice"), 30);
+assert_eq!(ages.get("alice"), Some(&30));
+```
+*/
+#[derive(Clone, Default)]
+pub struct SharedStr(Pierce<Arc<String>>);
+
+str_newtype!(SharedStr, Arc);
+
+impl From<Arc<String>> for SharedStr {
+    #[inline]
+    fn from(arc: Arc<String>) -> Self {
+        Self(Pierce::new(arc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashMap};
+
+    #[test]
+    fn test_map_lookup_by_str() {
+        let mut map = HashMap::new();
+        map.insert(SharedStr::from("one"), 1);
+        map.insert(SharedStr::from(String::from("two")), 2);
+        assert_eq!(map.get("one"), Some(&1));
+        assert_eq!(map.get("two"), Some(&2));
+        assert_eq!(map.get("three"), None);
+    }
+
+    #[test]
+    fn test_ordering_and_equality() {
+        let set: BTreeSet<SharedStr> = ["pear", "apple", "fig"]
+            .iter()
+            .copied()
+            .map(SharedStr::from)
+            .collect();
+        let sorted: Vec<&str> = set.iter().map(SharedStr::as_str).collect();
+        assert_eq!(sorted, ["apple", "fig", "pear"]);
+        assert!(set.contains("fig"));
+
+        let a = SharedStr::from("hello");
+        let b = a.clone();
+        assert_eq!(a.as_ptr(), b.as_ptr());
+        assert_eq!(a, SharedStr::from("hello"));
+        assert!(a == "hello");
+        assert!(a == *"hello");
+        let owned = String::from("hello");
+        assert!(a == owned);
+        assert_eq!(a.to_string(), "hello");
+        assert_eq!(format!("{:?}", a), "\"hello\"");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let s: SharedStr = serde_json::from_str("\"hello\"").unwrap();
+        assert_eq!(s, "hello");
+        assert_eq!(serde_json::to_string(&s).unwrap(), "\"hello\"");
+    }
+}
@@ -0,0 +1,141 @@
+use core::slice::SliceIndex;
+
+use crate::{Pierce, StableDeref};
+
+/* Slice methods, for Pierces whose target is a slice, like `Pierce<Arc<Vec<U>>>`.
+These are all reachable through deref already. Having them on Pierce makes them show up in its docs,
+and saves a `(*pierce)` where method resolution needs help. */
+impl<T, U> Pierce<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref<Target = [U]>,
+{
+    /** Get an element or a subslice, or `None` if the index is out of bounds.
+
+    This is [`slice::get`] on the cached target.
+    ```
+    # use std::sync::Arc;
+    # use pierce::Pierce;
+    let pierce = Pierce::new(Arc::new(vec![1, 2, 3]));
+    assert_eq!(pierce.get(0), Some(&1));
+    assert_eq!(pierce.get(1..), Some(&[2, 3][..]));
+    assert_eq!(pierce.get(3), None);
+    ```
+     */
+    #[inline]
+    pub fn get<'a, I>(&'a self, index: I) -> Option<&'a I::Output>
+    where
+        I: SliceIndex<[U]>,
+        U: 'a,
+    {
+        (**self).get(index)
+    }
+
+    /** Get an element or a subslice, without bounds checking.
+
+    This is [`slice::get_unchecked`] on the cached target.
+
+    # Safety
+    The index must be in bounds, see [`slice::get_unchecked`].
+     */
+    #[inline]
+    pub unsafe fn get_unchecked<'a, I>(&'a self, index: I) -> &'a I::Output
+    where
+        I: SliceIndex<[U]>,
+        U: 'a,
+    {
+        (**self).get_unchecked(index)
+    }
+
+    /** The number of elements in the target slice.
+     */
+    #[inline]
+    pub fn len(&self) -> usize {
+        (**self).len()
+    }
+
+    /** Whether the target slice has no elements.
+     */
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        (**self).is_empty()
+    }
+
+    /** The first element, or `None` if the target slice is empty.
+     */
+    #[inline]
+    pub fn first(&self) -> Option<&U> {
+        (**self).first()
+    }
+
+    /** The last element, or `None` if the target slice is empty.
+     */
+    #[inline]
+    pub fn last(&self) -> Option<&U> {
+        (**self).last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_get() {
+        let pierce = Pierce::new(Rc::new(vec![10, 20, 30]));
+        assert_eq!(pierce.get(2), Some(&30));
+        assert_eq!(pierce.get(..=1), Some(&[10, 20][..]));
+        assert_eq!(pierce.get(5), None);
+        assert_eq!(pierce.get(2..5), None);
+        assert!(std::ptr::eq(pierce.get(0).unwrap(), &pierce[0]));
+
+        let empty = Pierce::new(Box::new(Vec::<u8>::new()));
+        assert_eq!(empty.get(0), None);
+        assert_eq!(empty.get(..), Some(&[][..]));
+    }
+
+    #[test]
+    fn test_get_unchecked() {
+        let pierce = Pierce::new(Box::new(Box::<[u8]>::from(*b"abc")));
+        assert_eq!(unsafe { *pierce.get_unchecked(1) }, b'b');
+        assert_eq!(unsafe { pierce.get_unchecked(1..) }, b"bc");
+    }
+
+    #[test]
+    fn test_len_first_last() {
+        let pierce = Pierce::new(Rc::new(vec!['a', 'b', 'c']));
+        assert_eq!(pierce.len(), 3);
+        assert!(!pierce.is_empty());
+        assert_eq!(pierce.first(), Some(&'a'));
+        assert_eq!(pierce.last(), Some(&'c'));
+        assert!(std::ptr::eq(pierce.last().unwrap(), &pierce[2]));
+        let mut count = 0;
+        for i in 0..pierce.len() {
+            count += pierce[i].len_utf8();
+        }
+        assert_eq!(count, 3);
+
+        let empty = Pierce::new(Box::new(Box::<[u8]>::default()));
+        assert_eq!(empty.len(), 0);
+        assert!(empty.is_empty());
+        assert_eq!(empty.first(), None);
+        assert_eq!(empty.last(), None);
+    }
+
+    #[test]
+    fn test_other_targets_keep_their_methods() {
+        // These only exist on slice targets, so other targets' own methods are still reachable.
+        let cell = Pierce::new(Box::new(Box::new(Cell::new(7))));
+        assert_eq!(cell.get(), 7);
+        let map = Pierce::new(Rc::new(Box::new(std::collections::HashMap::from([(
+            1, "one",
+        )]))));
+        assert_eq!(map.get(&1), Some(&"one"));
+        assert_eq!(map.len(), 1);
+        let s = Pierce::new(Box::new(String::from("hé")));
+        assert_eq!(s.len(), 3);
+        assert!(!s.is_empty());
+    }
+}
@@ -0,0 +1,67 @@
+use alloc::vec::Vec;
+
+use crate::{Pierce, StableDeref};
+
+/** Batch operations on `Vec<Pierce<T>>`.
+
+```
+# use pierce::{Pierce, PierceVecExt};
+let pierces: Vec<Pierce<Box<Vec<i32>>>> =
+    Vec::pierce_all(vec![Box::new(vec![1]), Box::new(vec![2, 3])]);
+assert_eq!(*pierces[1], [2, 3]);
+let outers: Vec<Box<Vec<i32>>> = pierces.into_outers();
+assert_eq!(outers.len(), 2);
+```
+*/
+pub trait PierceVecExt<T>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    /** Wrap every outer pointer in a [`Pierce`].
+
+    This is the batch alternative to `.into_iter().map(Pierce::new).collect()`.
+     */
+    fn pierce_all(items: Vec<T>) -> Vec<Pierce<T>>;
+
+    /** Unwrap every Pierce, returning the outer pointers in order.
+     */
+    fn into_outers(self) -> Vec<T>;
+}
+
+impl<T> PierceVecExt<T> for Vec<Pierce<T>>
+where
+    T: StableDeref,
+    T::Target: StableDeref,
+{
+    #[inline]
+    fn pierce_all(items: Vec<T>) -> Vec<Pierce<T>> {
+        items.into_iter().map(Pierce::new).collect()
+    }
+
+    #[inline]
+    fn into_outers(self) -> Vec<T> {
+        self.into_iter().map(Pierce::into_outer).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_pierce_all_round_trip() {
+        let arcs: Vec<Arc<String>> = vec![Arc::new("a".into()), Arc::new("bc".into())];
+        let targets: Vec<*const str> = arcs.iter().map(|a| &***a as *const str).collect();
+        let pierces = Vec::pierce_all(arcs.clone());
+        for (p, t) in pierces.iter().zip(targets.iter()) {
+            assert_eq!(&**p as *const str, *t);
+        }
+        let outers = pierces.into_outers();
+        assert!(outers
+            .iter()
+            .zip(arcs.iter())
+            .all(|(a, b)| Arc::ptr_eq(a, b)));
+    }
+}
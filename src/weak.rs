@@ -0,0 +1,213 @@
+use alloc::{rc, sync};
+
+use crate::{Pierce, StableDeref};
+
+/** Outer pointers that have a weak counterpart, like [`Arc`][std::sync::Arc] and [`Rc`][std::rc::Rc].
+*/
+pub trait Downgrade: StableDeref + Sized
+where
+    Self::Target: StableDeref,
+{
+    /** The weak pointer type, e.g. `Weak<U>` for `Arc<U>`.
+     */
+    type Weak;
+
+    /** Create a weak pointer to the same allocation.
+     */
+    fn downgrade(this: &Self) -> Self::Weak;
+
+    /** Get a strong pointer back, or `None` if the allocation has been dropped.
+     */
+    fn upgrade(weak: &Self::Weak) -> Option<Self>;
+}
+
+impl<U: StableDeref> Downgrade for sync::Arc<U> {
+    type Weak = sync::Weak<U>;
+    #[inline]
+    fn downgrade(this: &Self) -> Self::Weak {
+        sync::Arc::downgrade(this)
+    }
+    #[inline]
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        weak.upgrade()
+    }
+}
+
+impl<U: StableDeref> Downgrade for rc::Rc<U> {
+    type Weak = rc::Weak<U>;
+    #[inline]
+    fn downgrade(this: &Self) -> Self::Weak {
+        rc::Rc::downgrade(this)
+    }
+    #[inline]
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        weak.upgrade()
+    }
+}
+
+/** A weak handle to a [`Pierce`].
+
+A `WeakPierce<T>` stores just the weak pointer, with no cached target, since the target might be dropped.
+Upgrading it creates a fresh Pierce, caching the target again.
+
+```
+# use std::sync::Arc;
+# use pierce::Pierce;
+let pierce = Pierce::new(Arc::new(vec![1, 2, 3]));
+let weak = pierce.downgrade();
+assert_eq!(&*weak.upgrade().unwrap(), &[1, 2, 3]);
+drop(pierce);
+assert!(weak.upgrade().is_none());
+```
+*/
+pub struct WeakPierce<T>
+where
+    T: Downgrade,
+    T::Target: StableDeref,
+{
+    weak: T::Weak,
+}
+
+impl<T> WeakPierce<T>
+where
+    T: Downgrade,
+    T::Target: StableDeref,
+{
+    /** Try to get a Pierce back.
+
+    Returns `None` if the allocation has been dropped.
+    Otherwise, the upgraded outer pointer is double-deref-ed and cached, just like in [`Pierce::new`].
+     */
+    #[inline]
+    pub fn upgrade(&self) -> Option<Pierce<T>> {
+        T::upgrade(&self.weak).map(Pierce::new)
+    }
+
+    /** Borrow the underlying weak pointer, e.g. the `Weak<U>` for a `WeakPierce<Arc<U>>`.
+     */
+    #[inline]
+    pub fn as_weak(&self) -> &T::Weak {
+        &self.weak
+    }
+
+    /** Get the underlying weak pointer out.
+
+    The weak pointer can be stored on its own, and upgraded back into a Pierce with [`WeakPierce::from_weak`].
+     */
+    #[inline]
+    pub fn into_weak(self) -> T::Weak {
+        self.weak
+    }
+
+    /** Wrap a weak pointer, e.g. one made with `Arc::downgrade`.
+     */
+    #[inline]
+    pub fn from_weak(weak: T::Weak) -> Self {
+        Self { weak }
+    }
+}
+
+impl<T> Clone for WeakPierce<T>
+where
+    T: Downgrade,
+    T::Target: StableDeref,
+    T::Weak: Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            weak: self.weak.clone(),
+        }
+    }
+}
+
+impl<T> Pierce<T>
+where
+    T: Downgrade,
+    T::Target: StableDeref,
+{
+    /** Create a [`WeakPierce`] pointing to the same allocation.
+
+    For the plain `Weak<U>` of a `Pierce<Arc<U>>`, use [`into_weak`][WeakPierce::into_weak] on the result.
+    ```
+    # use std::sync::{Arc, Weak};
+    # use pierce::Pierce;
+    let pierce = Pierce::new(Arc::new(String::from("hello")));
+    let weak: Weak<String> = pierce.downgrade().into_weak();
+    assert_eq!(weak.upgrade().unwrap().as_str(), "hello");
+    ```
+     */
+    #[inline]
+    pub fn downgrade(&self) -> WeakPierce<T> {
+        WeakPierce {
+            weak: T::downgrade(self.borrow_outer()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_upgrade_same_target() {
+        let pierce = Pierce::new(Arc::new(String::from("hello")));
+        let weak = pierce.downgrade();
+        let upgraded = weak.upgrade().unwrap();
+        assert_eq!(&*upgraded as *const str, &*pierce as *const str);
+        drop(upgraded);
+        drop(pierce);
+        assert!(weak.upgrade().is_none());
+        assert!(weak.clone().upgrade().is_none());
+    }
+
+    #[test]
+    fn test_weak_round_trip() {
+        let pierce = Pierce::new(Rc::new(vec![1, 2]));
+        let weak = pierce.downgrade();
+        assert_eq!(pierce.weak_count(), 1);
+        assert!(weak.as_weak().ptr_eq(&Rc::downgrade(pierce.borrow_outer())));
+        let raw: std::rc::Weak<Vec<i32>> = weak.into_weak();
+        let weak = WeakPierce::<Rc<Vec<i32>>>::from_weak(raw);
+        assert_eq!(weak.upgrade().unwrap().as_ptr(), pierce.as_ptr());
+        drop(pierce);
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    #[allow(clippy::redundant_allocation)]
+    fn test_cycle_freed() {
+        struct Node {
+            parent: RefCell<Option<WeakPierce<Rc<Box<Node>>>>>,
+            child: RefCell<Option<Pierce<Rc<Box<Node>>>>>,
+            drops: Rc<Cell<usize>>,
+        }
+        impl Drop for Node {
+            fn drop(&mut self) {
+                self.drops.set(self.drops.get() + 1);
+            }
+        }
+        let drops = Rc::new(Cell::new(0));
+        let new_node = || {
+            Pierce::new(Rc::new(Box::new(Node {
+                parent: RefCell::new(None),
+                child: RefCell::new(None),
+                drops: drops.clone(),
+            })))
+        };
+        let parent = new_node();
+        let child = new_node();
+        *child.parent.borrow_mut() = Some(parent.downgrade());
+        *parent.child.borrow_mut() = Some(child.clone());
+        let upgraded = child.parent.borrow().as_ref().unwrap().upgrade().unwrap();
+        assert_eq!(&*upgraded as *const Node, &*parent as *const Node);
+        drop(upgraded);
+        drop(child);
+        assert_eq!(drops.get(), 0);
+        drop(parent);
+        assert_eq!(drops.get(), 2);
+    }
+}
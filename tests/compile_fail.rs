@@ -0,0 +1,7 @@
+// Miri can't run the compiler.
+#[test]
+#[cfg_attr(miri, ignore)]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
+}
@@ -0,0 +1,11 @@
+use pierce::PierceBorrowed;
+use std::sync::Arc;
+
+fn main() {
+    let borrowed;
+    {
+        let arc = Arc::new(vec![1, 2, 3]);
+        borrowed = PierceBorrowed::new(&arc);
+    }
+    assert_eq!(borrowed.len(), 3);
+}
@@ -0,0 +1,10 @@
+use pierce::PierceBorrowed;
+
+fn first(names: Box<Vec<String>>) -> &'static str {
+    let borrowed = PierceBorrowed::new(&names);
+    &borrowed.target()[0]
+}
+
+fn main() {
+    first(Box::new(vec![String::from("a")]));
+}
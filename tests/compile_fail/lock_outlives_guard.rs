@@ -0,0 +1,10 @@
+use std::sync::RwLock;
+
+fn main() {
+    let pierce;
+    {
+        let lock = RwLock::new(vec![1, 2, 3]);
+        pierce = pierce::lock::read(&lock).unwrap();
+    }
+    assert_eq!(pierce.len(), 3);
+}
@@ -0,0 +1,6 @@
+use pierce::Pierce;
+use std::sync::Arc;
+
+fn main() {
+    let _pierce = Pierce::new(Arc::new(5));
+}
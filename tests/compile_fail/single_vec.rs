@@ -0,0 +1,5 @@
+use pierce::Pierce;
+
+fn main() {
+    let _pierce = Pierce::new(vec![1u8, 2, 3]);
+}